@@ -2,9 +2,18 @@
 //!
 //! Usage: lantern-teleport <save_file> [--location <LOCATION>]
 
-use clap::Parser;
-use lantern_teleport_core::{get_all_locations, search_locations, teleport, Location};
-use std::path::PathBuf;
+use clap::{Parser, Subcommand};
+#[cfg(feature = "experimental")]
+use lantern_teleport_core::lantern_report;
+use lantern_teleport_core::{
+    BackupConfig, Bookmark, BookmarkStore, Catalog, CharacterInfo, CurrentPosition, JournalAction,
+    Location, MapId, SaveFile, TeleportError, TeleportOptions, copy_position, get_all_locations,
+    identify_position, inspect_backup, last_undoable, list_backups, locations_by_region, nudge,
+    parse_coordinates, read_journal, restore_backup, scan_slots, search_locations,
+    teleport_to_bookmark, teleport_with_options, undo_last_teleport, validate_save_file,
+};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -15,35 +24,265 @@ use std::path::PathBuf;
     long_about = "A CLI tool to teleport to any Lantern in Bloodborne save files.\n\n\
                   This tool is meant to be run on userdata0000, userdata0001, etc. files \
                   found in your Bloodborne save directory.\
-                  userdata0000 is your first character, userdata0001 is your second character, and so on."
+                  userdata0000 is your first character, userdata0001 is your second character, and so on.",
+    args_conflicts_with_subcommands = true
 )]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Path to the save file (e.g., userdata0000, userdata0001)
     #[arg( num_args = 0..=1)]
     save_file: Option<PathBuf>,
 
     /// Teleport to a specific location (supports fuzzy matching)
-    #[arg(short, long)]
+    #[arg(short, long, conflicts_with = "coords")]
     location: Option<String>,
 
+    /// Teleport to a saved bookmark (see the `bookmark` command)
+    #[arg(short, long, conflicts_with_all = ["location", "coords"])]
+    bookmark: Option<String>,
+
+    /// Teleport to exact coordinates instead of a lantern (requires --map)
+    #[arg(
+        long,
+        value_name = "X,Y,Z",
+        value_parser = parse_coords,
+        allow_hyphen_values = true,
+        requires = "map"
+    )]
+    coords: Option<[f32; 3]>,
+
+    /// Map ID for --coords, as two hex bytes (e.g. 00:15 for Hunter's Dream)
+    #[arg(long, value_name = "AA:BB", value_parser = parse_map_id, requires = "coords")]
+    map: Option<MapId>,
+
+    /// Direction to face on arrival, in degrees (the current facing is kept
+    /// otherwise, even for catalog locations with a facing). Experimental: the
+    /// facing offset has not been checked against real saves
+    #[arg(
+        long,
+        value_name = "DEGREES",
+        allow_hyphen_values = true,
+        conflicts_with = "bookmark"
+    )]
+    facing: Option<f32>,
+
     /// List all available locations
     #[arg(long)]
     list: bool,
+
+    /// List the backups of the save file, newest first
+    #[arg(long)]
+    list_backups: bool,
+
+    /// Restore the backup with the given number (as shown by --list-backups)
+    #[arg(long, value_name = "NUMBER")]
+    restore_backup: Option<usize>,
+
+    /// Maximum number of backups to keep for the save file
+    #[arg(long, value_name = "COUNT", global = true)]
+    max_backups: Option<usize>,
+
+    /// Do not create a backup before modifying the save file
+    #[arg(long, global = true)]
+    no_backup: bool,
+
+    /// Write to the first coordinate block even if the save has several
+    /// plausible ones (see the `info` command)
+    #[arg(long)]
+    allow_ambiguous: bool,
+
+    /// Do not warn about The Old Hunters destinations in saves that show no
+    /// DLC progress (for when the DLC is installed)
+    #[arg(long)]
+    force_dlc: bool,
+
+    /// Use a different bookmarks file instead of the per-user one
+    #[arg(long, value_name = "PATH", global = true)]
+    bookmarks_file: Option<PathBuf>,
+
+    /// Load extra locations from a TOML or JSON catalog file (can be repeated)
+    #[arg(long, value_name = "PATH", global = true)]
+    catalog: Vec<PathBuf>,
 }
 
-/// Display all available locations grouped by region
-fn list_locations() {
-    let locations = get_all_locations();
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Show the current position of a save file and the nearest lantern
+    Info {
+        /// Path to the save file
+        save_file: PathBuf,
+    },
+    /// Show which lanterns a character has lit (experimental: the lantern
+    /// flags are unverified)
+    #[cfg(feature = "experimental")]
+    Lanterns {
+        /// Path to the save file
+        save_file: PathBuf,
+    },
+    /// Show the teleports recorded in the journal of a save file
+    History {
+        /// Path to the save file
+        save_file: PathBuf,
+    },
+    /// Undo the last teleport of a save file, restoring the previous position
+    Undo {
+        /// Path to the save file
+        save_file: PathBuf,
+    },
+    /// Move the character of one save to where the character of another save stands
+    CopyPosition {
+        /// Save file whose position is copied (only read)
+        #[arg(long, value_name = "SAVE_FILE")]
+        from: PathBuf,
+        /// Save file whose character is moved
+        #[arg(long, value_name = "SAVE_FILE")]
+        to: PathBuf,
+        /// Write to the first coordinate block even if the save has several
+        /// plausible ones
+        #[arg(long)]
+        allow_ambiguous: bool,
+        /// Do not warn about positions in The Old Hunters maps in saves that
+        /// show no DLC progress (for when the DLC is installed)
+        #[arg(long)]
+        force_dlc: bool,
+        /// Copy the facing too. Experimental: the facing offset has not been
+        /// checked against real saves
+        #[arg(long)]
+        copy_facing: bool,
+    },
+    /// Move the character by a small offset, e.g. to free it from a wall
+    #[command(group(clap::ArgGroup::new("offset").required(true).multiple(true)))]
+    Nudge {
+        /// Path to the save file
+        save_file: PathBuf,
+        /// Offset along the X axis
+        #[arg(long, group = "offset", allow_hyphen_values = true)]
+        dx: Option<f32>,
+        /// Offset along the Y axis (up)
+        #[arg(long, group = "offset", allow_hyphen_values = true)]
+        dy: Option<f32>,
+        /// Offset along the Z axis
+        #[arg(long, group = "offset", allow_hyphen_values = true)]
+        dz: Option<f32>,
+        /// Write to the first coordinate block even if the save has several
+        /// plausible ones
+        #[arg(long)]
+        allow_ambiguous: bool,
+    },
+    /// Show every character slot (userdata0000, userdata0001, ...) in a save directory
+    Slots {
+        /// Path to the save directory
+        dir: PathBuf,
+    },
+    /// Manage bookmarks of saved positions
+    #[command(subcommand)]
+    Bookmark(BookmarkCommand),
+}
+
+#[derive(Subcommand, Debug)]
+enum BookmarkCommand {
+    /// Save the current position of a save file as a bookmark
+    Add {
+        /// Name of the bookmark
+        name: String,
+        /// Path to the save file to capture the position from
+        save_file: PathBuf,
+    },
+    /// List all bookmarks
+    List,
+    /// Remove a bookmark
+    Rm {
+        /// Name of the bookmark
+        name: String,
+    },
+}
 
-    // Group by region
-    let mut regions: Vec<(&str, Vec<&Location>)> = Vec::new();
-    for loc in locations {
-        if let Some(existing) = regions.iter_mut().find(|(name, _)| *name == loc.region) {
-            existing.1.push(loc);
-        } else {
-            regions.push((loc.region, vec![loc]));
+impl Args {
+    /// Build the backup configuration from the command line flags
+    fn backup_config(&self) -> BackupConfig {
+        let mut config = BackupConfig {
+            enabled: !self.no_backup,
+            ..BackupConfig::default()
+        };
+        if self.max_backups.is_some() {
+            config.max_backups = self.max_backups;
         }
+        config
+    }
+}
+
+/// Parse a coordinate triple in the form "x,y,z"
+fn parse_coords(value: &str) -> Result<[f32; 3], String> {
+    parse_coordinates(value).map_err(|e| e.to_string())
+}
+
+/// Parse a known map ID in the form "AA:BB" (two hex bytes)
+fn parse_map_id(value: &str) -> Result<MapId, String> {
+    value.parse::<MapId>().map_err(|_| {
+        let known: Vec<String> = MapId::all()
+            .map(|id| format!("{} ({})", id.to_hex(), id))
+            .collect();
+        format!(
+            "'{}' is not a known map ID; known map IDs: {}",
+            value,
+            known.join(", ")
+        )
+    })
+}
+
+/// Describe a 4-byte save file map ID by name, falling back to hex
+fn describe_map_id(map_id: [u8; 4]) -> String {
+    match MapId::from_save_format(map_id) {
+        Ok(map) => format!("{} ({})", map, map.to_hex()),
+        Err(_) => format!(
+            "Unknown map {:02X}{:02X}{:02X}{:02X}",
+            map_id[0], map_id[1], map_id[2], map_id[3]
+        ),
+    }
+}
+
+/// Format a play time as hours:minutes:seconds
+fn format_playtime(playtime: Duration) -> String {
+    let secs = playtime.as_secs();
+    format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/// Describe an NG+ cycle ("NG" for the first playthrough, then "NG+1", ...)
+fn describe_cycle(cycle: u32) -> String {
+    match cycle {
+        0 => "NG".to_string(),
+        n => format!("NG+{}", n),
     }
+}
+
+/// Display a character field, or "unknown" if it could not be read
+fn or_unknown<T: std::fmt::Display>(value: Option<T>) -> String {
+    value.map_or_else(|| "unknown".to_string(), |v| v.to_string())
+}
+
+/// One-line summary of a character: name, level and NG+ cycle
+fn describe_character(character: &CharacterInfo) -> String {
+    let mut parts = vec![
+        character
+            .name
+            .clone()
+            .unwrap_or_else(|| "Unnamed".to_string()),
+    ];
+    if let Some(level) = character.level {
+        parts.push(format!("level {}", level));
+    }
+    if let Some(cycle) = character.ng_cycle {
+        parts.push(describe_cycle(cycle));
+    }
+    parts.join(", ")
+}
+
+/// Display all available locations grouped by region
+fn list_locations() {
+    let locations = get_all_locations();
+    let regions = locations_by_region();
 
     let region_count = regions.len();
 
@@ -69,6 +308,427 @@ fn list_locations() {
     );
 }
 
+/// Display the position of a save file, its map area, the nearest lantern
+/// and every candidate coordinate block
+fn print_info(save_file: &Path) {
+    let (save, position) = match SaveFile::load(save_file).and_then(|save| {
+        let position = save.position()?;
+        Ok((save, position))
+    }) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            println!("Error: Failed to read position: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let facing = save.facing();
+    let candidates = save.coordinate_candidates();
+    let character = save.character();
+    let dlc = save.has_dlc_progress().ok();
+
+    println!("Save file: {:?}", save_file);
+    // The character offsets are unverified
+    println!(
+        "Character (unverified): {}",
+        or_unknown(character.name.as_ref())
+    );
+    println!("Level: {}", or_unknown(character.level));
+    println!("Insight: {}", or_unknown(character.insight));
+    println!("Blood echoes: {}", or_unknown(character.blood_echoes));
+    println!(
+        "Play time: {}",
+        or_unknown(character.playtime.map(format_playtime))
+    );
+    println!(
+        "Cycle: {}",
+        or_unknown(character.ng_cycle.map(describe_cycle))
+    );
+    println!(
+        "Old Hunters DLC progress: {}",
+        or_unknown(dlc.map(|dlc| if dlc { "yes" } else { "no" }))
+    );
+    // The night and Blood Moon flags are unverified
+    #[cfg(feature = "experimental")]
+    println!("World state: {}", or_unknown(save.world_state().ok()));
+    println!("Map: {}", describe_map_id(position.map_id));
+    println!(
+        "Position: X: {:.2}, Y: {:.2}, Z: {:.2}",
+        position.x, position.y, position.z
+    );
+    println!(
+        "Facing: {}",
+        or_unknown(facing.map(|facing| format!("{:.0}°", facing)))
+    );
+
+    match identify_position(&position) {
+        Some(info) => {
+            println!("Area: {}", info.map.area());
+            if let Some(nearest) = info.nearest {
+                println!(
+                    "Near: {} lantern ({:.1} units)",
+                    nearest.location.name, nearest.distance
+                );
+            }
+        }
+        None => println!("Area: Unknown map"),
+    }
+
+    println!("\nCoordinate candidates (the first one is used):");
+    for candidate in &candidates {
+        println!(
+            "  {:#010X}  X: {:.2}, Y: {:.2}, Z: {:.2}  score {:.2}{}",
+            candidate.offset,
+            candidate.x,
+            candidate.y,
+            candidate.z,
+            candidate.score,
+            if candidate.is_plausible() {
+                ""
+            } else {
+                " (implausible)"
+            }
+        );
+    }
+    if candidates.iter().filter(|c| c.is_plausible()).count() > 1 {
+        println!(
+            "Warning: several candidates are plausible; teleporting requires --allow-ambiguous"
+        );
+    }
+}
+
+/// Display the lanterns of every region and whether the save has lit them
+#[cfg(feature = "experimental")]
+fn print_lanterns(save_file: &Path) {
+    let statuses = match lantern_report(save_file) {
+        Ok(statuses) => statuses,
+        Err(e) => {
+            println!("Error: Failed to read lantern flags: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let lit = statuses
+        .iter()
+        .filter(|status| status.unlocked == Some(true))
+        .count();
+    println!(
+        "\nLanterns lit in {:?}: {}/{}",
+        save_file,
+        lit,
+        statuses.len()
+    );
+    println!("============================");
+
+    for (region, locations) in locations_by_region() {
+        println!("\n{}:", region);
+        for location in locations {
+            let unlocked = statuses
+                .iter()
+                .find(|status| std::ptr::eq(status.location, location))
+                .and_then(|status| status.unlocked);
+            let mark = match unlocked {
+                Some(true) => "✓",
+                Some(false) => "✗",
+                None => "?",
+            };
+            println!("  {} {}", mark, location.name);
+        }
+    }
+}
+
+/// Display every character slot in a save directory
+fn print_slots(dir: &Path) {
+    let slots = match scan_slots(dir) {
+        Ok(slots) => slots,
+        Err(e) => {
+            println!("Error: Failed to scan save directory: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if slots.is_empty() {
+        println!("No character slots (userdata00NN) found in {:?}", dir);
+        return;
+    }
+
+    println!("\nCharacter slots in {:?}:", dir);
+    println!("============================");
+    for slot in &slots {
+        let name = slot
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        match &slot.position {
+            Ok(position) => {
+                let area = slot
+                    .info
+                    .as_ref()
+                    .map(|info| info.map.name().to_string())
+                    .unwrap_or_else(|| describe_map_id(position.map_id));
+                let character = slot
+                    .character
+                    .as_ref()
+                    .map(describe_character)
+                    .unwrap_or_default();
+                println!("  Slot {} ({}): {}", slot.index, name, character);
+                println!(
+                    "      {}  X: {:.2}, Y: {:.2}, Z: {:.2}",
+                    area, position.x, position.y, position.z
+                );
+                if let Some(nearest) = slot.info.as_ref().and_then(|info| info.nearest.as_ref()) {
+                    println!(
+                        "      Near: {} lantern ({:.1} units)",
+                        nearest.location.name, nearest.distance
+                    );
+                }
+            }
+            Err(e) => println!("  Slot {} ({}): invalid: {}", slot.index, name, e),
+        }
+    }
+}
+
+/// Load the bookmarks file, either the given one or the per-user default
+fn load_bookmarks(path: Option<&Path>) -> BookmarkStore {
+    let result = match path {
+        Some(path) => BookmarkStore::load(path),
+        None => BookmarkStore::load_default(),
+    };
+
+    match result {
+        Ok(store) => store,
+        Err(e) => {
+            println!("Error: Failed to load bookmarks: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Display all bookmarks
+fn print_bookmarks(store: &BookmarkStore) {
+    println!("\nBookmarks ({}):", store.path().display());
+    println!("----------------------------");
+
+    if store.bookmarks().is_empty() {
+        println!("  (none)");
+        return;
+    }
+
+    for bookmark in store.bookmarks() {
+        println!(
+            "  - {} (Map: {}, X: {:.2}, Y: {:.2}, Z: {:.2})",
+            bookmark.name,
+            describe_map_id(bookmark.map_id),
+            bookmark.x,
+            bookmark.y,
+            bookmark.z
+        );
+    }
+}
+
+/// Handle the `bookmark` subcommands
+fn run_bookmark_command(command: BookmarkCommand, bookmarks_file: Option<&Path>) {
+    let mut store = load_bookmarks(bookmarks_file);
+
+    match command {
+        BookmarkCommand::Add { name, save_file } => {
+            let position = match validate_save_file(&save_file) {
+                Ok(position) => position,
+                Err(e) => {
+                    println!("Error: Failed to read position: {}", e);
+                    std::process::exit(1);
+                }
+            };
+
+            let bookmark = Bookmark::from_position(name, &position);
+            let result = store.add(bookmark.clone()).and_then(|_| store.save());
+            if let Err(e) = result {
+                println!("Error: Failed to add bookmark: {}", e);
+                std::process::exit(1);
+            }
+
+            println!(
+                "Bookmarked '{}' (X: {:.2}, Y: {:.2}, Z: {:.2})",
+                bookmark.name, bookmark.x, bookmark.y, bookmark.z
+            );
+        }
+        BookmarkCommand::List => print_bookmarks(&store),
+        BookmarkCommand::Rm { name } => {
+            let result = store.remove(&name).and_then(|b| store.save().map(|_| b));
+            match result {
+                Ok(bookmark) => println!("Removed bookmark '{}'", bookmark.name),
+                Err(e) => {
+                    println!("Error: Failed to remove bookmark: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+}
+
+/// Teleport a save file to a named bookmark
+fn teleport_to_named_bookmark(
+    save_file: &Path,
+    name: &str,
+    store: &BookmarkStore,
+    options: &TeleportOptions,
+) {
+    let Some(bookmark) = store.get(name) else {
+        println!(
+            "Error: No bookmark named '{}'\nUse `bookmark list` to see available bookmarks",
+            name
+        );
+        std::process::exit(1);
+    };
+
+    println!("Teleporting to bookmark: {}", bookmark.name);
+
+    match teleport_to_bookmark(save_file, bookmark, options) {
+        Ok(outcome) => {
+            println!("\nSuccessfully teleported to {}!", bookmark.name);
+            println!("Save file updated: {:?}", save_file);
+            if let Some(backup) = outcome.backup {
+                println!("Backup saved to: {}", backup.path.display());
+            }
+            for warning in &outcome.warnings {
+                println!("Warning: {}", warning);
+            }
+        }
+        Err(e) => exit_with_teleport_error(e),
+    }
+}
+
+/// Map and coordinates of a position on one line
+fn describe_position(position: &CurrentPosition) -> String {
+    format!(
+        "{}  X: {:.2}, Y: {:.2}, Z: {:.2}",
+        describe_map_id(position.map_id),
+        position.x,
+        position.y,
+        position.z
+    )
+}
+
+/// List the journaled teleports of a save file, oldest first
+fn print_history(save_file: &Path, config: &BackupConfig) {
+    let entries = match read_journal(save_file, config) {
+        Ok(entries) => entries,
+        Err(e) => {
+            println!("Error: Failed to read the journal: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if entries.is_empty() {
+        println!("No teleports recorded for {:?}", save_file);
+        return;
+    }
+
+    println!("\nTeleport history of {:?} (oldest first):", save_file);
+    println!("============================");
+    for (i, entry) in entries.iter().enumerate() {
+        let time = entry
+            .time
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let action = match entry.action {
+            JournalAction::Teleport => "Teleport to",
+            JournalAction::Undo => "Undo of",
+        };
+        println!(
+            "  {}. {} {} (unix time {})",
+            i + 1,
+            action,
+            entry.target,
+            time
+        );
+        println!("     from {}", describe_position(&entry.from));
+        println!("     to   {}", describe_position(&entry.to));
+    }
+
+    match last_undoable(&entries) {
+        Some(entry) => println!(
+            "\n`undo` restores the position before the teleport to {}",
+            entry.target
+        ),
+        None => println!("\nEvery teleport has been undone"),
+    }
+}
+
+/// Undo the last journaled teleport of a save file
+fn undo_teleport(save_file: &Path, config: &BackupConfig) {
+    let options = TeleportOptions {
+        backups: config.clone(),
+        ..TeleportOptions::default()
+    };
+
+    match undo_last_teleport(save_file, &options) {
+        Ok((undone, outcome)) => {
+            println!("Undid the teleport to {}", undone.target);
+            println!("Restored: {}", describe_position(&undone.from));
+            if let Some(backup) = outcome.backup {
+                println!("Backup saved to: {}", backup.path.display());
+            }
+            for warning in &outcome.warnings {
+                println!("Warning: {}", warning);
+            }
+        }
+        Err(e) => {
+            println!("Error: Failed to undo: {}", e);
+            if let TeleportError::JournalMismatch(_) = e {
+                println!("Use --list-backups and --restore-backup to go back to an older copy");
+            }
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Move the character of `to` to the position of `from`
+fn copy_position_between(from: &Path, to: &Path, options: &TeleportOptions) {
+    match copy_position(from, to, options) {
+        Ok((position, outcome)) => {
+            println!("Copied the position of {:?} to {:?}", from, to);
+            println!("Position: {}", describe_position(&position));
+            if let Some(backup) = outcome.backup {
+                println!("Backup saved to: {}", backup.path.display());
+            }
+            for warning in &outcome.warnings {
+                println!("Warning: {}", warning);
+            }
+        }
+        Err(e) => exit_with_teleport_error(e),
+    }
+}
+
+/// Move the character of a save file by an offset along each axis
+fn nudge_character(save_file: &Path, [dx, dy, dz]: [f32; 3], options: &TeleportOptions) {
+    match nudge(save_file, dx, dy, dz, options) {
+        Ok((position, outcome)) => {
+            println!("Nudged by X: {:+.2}, Y: {:+.2}, Z: {:+.2}", dx, dy, dz);
+            println!("Position: {}", describe_position(&position));
+            if let Some(backup) = outcome.backup {
+                println!("Backup saved to: {}", backup.path.display());
+            }
+            for warning in &outcome.warnings {
+                println!("Warning: {}", warning);
+            }
+        }
+        Err(e) => exit_with_teleport_error(e),
+    }
+}
+
+/// Report a failed teleport, naming the flag that overrides the failed check
+fn exit_with_teleport_error(e: TeleportError) -> ! {
+    println!("Error: Failed to teleport: {}", e);
+    if let TeleportError::MultipleCoordinateBlocks { .. } = e {
+        println!("Use --allow-ambiguous to write to the first block anyway");
+    }
+    std::process::exit(1);
+}
+
 /// Handle search results and return the location if exactly one match
 /// Otherwise, prints error and exits
 fn handle_search_results(query: &str, matches: Vec<&'static Location>) -> &'static Location {
@@ -98,11 +758,158 @@ fn handle_search_results(query: &str, matches: Vec<&'static Location>) -> &'stat
     location
 }
 
+/// Display all backups of a save file, newest first
+fn print_backups(save_file: &Path, config: &BackupConfig) {
+    let backups = match list_backups(save_file, config) {
+        Ok(backups) => backups,
+        Err(e) => {
+            println!("Error: Failed to list backups: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if backups.is_empty() {
+        println!("No backups found for {:?}", save_file);
+        return;
+    }
+
+    println!("\nBackups of {:?} (newest first):", save_file);
+    println!("============================");
+    for (i, backup) in backups.iter().enumerate() {
+        let created = backup
+            .created
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let position = match inspect_backup(backup) {
+            Ok(pos) => format!(
+                "Map: {}  X: {:.2}, Y: {:.2}, Z: {:.2}",
+                describe_map_id(pos.map_id),
+                pos.x,
+                pos.y,
+                pos.z
+            ),
+            Err(e) => format!("invalid: {}", e),
+        };
+        println!(
+            "  {}. {} ({} bytes, created at unix time {})",
+            i + 1,
+            backup.path.display(),
+            backup.size,
+            created
+        );
+        println!("     {}", position);
+    }
+}
+
+/// Restore the numbered backup (1 = newest) over the save file
+fn restore_numbered_backup(save_file: &Path, number: usize, config: &BackupConfig) {
+    let backups = match list_backups(save_file, config) {
+        Ok(backups) => backups,
+        Err(e) => {
+            println!("Error: Failed to list backups: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let Some(backup) = number.checked_sub(1).and_then(|i| backups.get(i)) else {
+        println!(
+            "Error: No backup numbered {} found\nUse --list-backups to see available backups",
+            number
+        );
+        std::process::exit(1);
+    };
+
+    match restore_backup(save_file, backup, config) {
+        Ok(()) => {
+            println!("Restored backup: {}", backup.path.display());
+            println!("Save file updated: {:?}", save_file);
+        }
+        Err(e) => {
+            println!("Error: Failed to restore backup: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Merge the per-user and command line catalog files into the built-in
+/// lanterns and install the result
+fn install_catalog(extra_files: &[PathBuf]) {
+    let mut catalog = Catalog::builtin();
+    let result = catalog.load_user_catalogs().and_then(|_| {
+        extra_files
+            .iter()
+            .try_for_each(|path| catalog.load_file(path))
+    });
+
+    if let Err(e) = result.and_then(|_| catalog.install()) {
+        println!("Error: Failed to load location catalog: {}", e);
+        std::process::exit(1);
+    }
+}
+
 fn main() {
     let args = Args::parse();
 
+    install_catalog(&args.catalog);
+
+    let backup_config = args.backup_config();
+
+    if let Some(command) = args.command {
+        match command {
+            Command::Info { save_file } => print_info(&save_file),
+            #[cfg(feature = "experimental")]
+            Command::Lanterns { save_file } => print_lanterns(&save_file),
+            Command::History { save_file } => print_history(&save_file, &backup_config),
+            Command::Undo { save_file } => undo_teleport(&save_file, &backup_config),
+            Command::CopyPosition {
+                from,
+                to,
+                allow_ambiguous,
+                force_dlc,
+                copy_facing,
+            } => {
+                let options = TeleportOptions {
+                    backups: backup_config.clone(),
+                    allow_ambiguous,
+                    force_dlc,
+                    write_facing: copy_facing,
+                };
+                copy_position_between(&from, &to, &options)
+            }
+            Command::Nudge {
+                save_file,
+                dx,
+                dy,
+                dz,
+                allow_ambiguous,
+            } => {
+                let options = TeleportOptions {
+                    backups: backup_config.clone(),
+                    allow_ambiguous,
+                    ..TeleportOptions::default()
+                };
+                let offset = [dx, dy, dz].map(|d| d.unwrap_or(0.0));
+                nudge_character(&save_file, offset, &options)
+            }
+            Command::Slots { dir } => print_slots(&dir),
+            Command::Bookmark(command) => {
+                run_bookmark_command(command, args.bookmarks_file.as_deref())
+            }
+        }
+        return;
+    }
+
     if args.list {
         list_locations();
+        // Bookmarks are optional extras, so a broken file should not hide the lanterns
+        let store = match args.bookmarks_file.as_deref() {
+            Some(path) => BookmarkStore::load(path),
+            None => BookmarkStore::load_default(),
+        };
+        if let Ok(store) = store {
+            print_bookmarks(&store);
+        }
         return;
     }
 
@@ -111,29 +918,85 @@ fn main() {
         std::process::exit(1);
     };
 
+    if args.list_backups {
+        print_backups(&save_file, &backup_config);
+        return;
+    }
+
+    if let Some(number) = args.restore_backup {
+        restore_numbered_backup(&save_file, number, &backup_config);
+        return;
+    }
+
+    let options = TeleportOptions {
+        backups: backup_config,
+        allow_ambiguous: args.allow_ambiguous,
+        force_dlc: args.force_dlc,
+        // The facing offset is unverified, so catalog facings are not written
+        // unless a facing is asked for explicitly
+        write_facing: args.facing.is_some(),
+    };
+
+    if let Some(name) = &args.bookmark {
+        let store = load_bookmarks(args.bookmarks_file.as_deref());
+        teleport_to_named_bookmark(&save_file, name, &store, &options);
+        return;
+    }
+
     // Get the location to teleport to
-    let location = match &args.location {
-        Some(name) => {
+    let custom_location;
+    let location = match (&args.location, args.coords, args.map) {
+        (_, Some([x, y, z]), Some(map)) => match Location::custom(x, y, z, map.to_bytes()) {
+            Ok(location) => {
+                custom_location = location;
+                &custom_location
+            }
+            Err(e) => {
+                println!("Error: Invalid destination: {}", e);
+                std::process::exit(1);
+            }
+        },
+        (Some(name), _, _) => {
             let matches = search_locations(name);
             handle_search_results(name, matches)
         }
-        None => {
+        _ => {
             // Default to first location (Hunter's Dream)
             &get_all_locations()[0]
         }
     };
 
-    println!("Teleporting to: {} in {}", location.name, location.region);
+    let turned_location;
+    let location = match args.facing {
+        Some(degrees) => match location.clone().with_facing(degrees) {
+            Ok(location) => {
+                turned_location = location;
+                &turned_location
+            }
+            Err(e) => {
+                println!("Error: Invalid destination: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => location,
+    };
 
+    println!("Teleporting to: {} in {}", location.name, location.region);
+    if let Some(facing) = location.facing.filter(|_| options.write_facing) {
+        println!("Facing: {:.0}°", facing);
+    }
     // Perform the teleport
-    match teleport(&save_file, location) {
-        Ok(()) => {
+    match teleport_with_options(&save_file, location, &options) {
+        Ok(outcome) => {
             println!("\nSuccessfully teleported to {}!", location.name);
             println!("Save file updated: {:?}", &save_file);
+            if let Some(backup) = outcome.backup {
+                println!("Backup saved to: {}", backup.path.display());
+            }
+            for warning in &outcome.warnings {
+                println!("Warning: {}", warning);
+            }
         }
-        Err(e) => {
-            println!("Error: Failed to teleport: {:?}", e);
-            std::process::exit(1);
-        }
+        Err(e) => exit_with_teleport_error(e),
     }
 }
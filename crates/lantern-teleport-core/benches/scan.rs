@@ -0,0 +1,77 @@
+//! Signature scanning on multi-megabyte synthetic saves.
+//!
+//! `naive` is the byte-by-byte window comparison the parser used before it
+//! switched to a single memchr-based pass; it is kept here as the baseline.
+//!
+//! Run with `cargo bench -p lantern-teleport-core`.
+
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use lantern_teleport_core::{LOCATIONS, teleport_bytes, validate_save_bytes};
+
+const LCED: [u8; 4] = *b"LCED";
+const PATTERN: [u8; 12] = [0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0, 0, 0, 0, 0];
+const SIZES_MIB: [usize; 3] = [1, 4, 16];
+
+/// A save of `len` bytes of noise with the LCED marker at 3/4 of the file and
+/// the coordinate block near the end, the worst case for both searches
+fn synthetic_save(len: usize) -> Vec<u8> {
+    // xorshift noise that never contains the marker or pattern bytes
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+    let mut bytes: Vec<u8> = (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state as u8) & 0x3F
+        })
+        .collect();
+
+    bytes[0x04..0x08].copy_from_slice(&[0x00, 0x00, 0x00, 0x15]);
+    let lced = len / 4 * 3;
+    bytes[lced..lced + 4].copy_from_slice(&LCED);
+    let pattern = len - 64;
+    bytes[pattern..pattern + 12].copy_from_slice(&PATTERN);
+    bytes[pattern + 12..pattern + 24].fill(0x40);
+    bytes
+}
+
+/// The original two-scan, byte-by-byte search
+fn naive_locate(bytes: &[u8]) -> Option<usize> {
+    let mut lced = None;
+    for i in 0..(bytes.len().saturating_sub(4)) {
+        if bytes[i..i + 4] == LCED {
+            lced = Some(i);
+            break;
+        }
+    }
+
+    let end = bytes.len().saturating_sub(PATTERN.len());
+    (lced?..end)
+        .find(|&i| bytes[i..i + PATTERN.len()] == PATTERN)
+        .map(|i| i + PATTERN.len())
+}
+
+fn bench_scan(c: &mut Criterion) {
+    let mut group = c.benchmark_group("locate");
+    for mib in SIZES_MIB {
+        let bytes = synthetic_save(mib << 20);
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("naive", mib), &bytes, |b, bytes| {
+            b.iter(|| naive_locate(black_box(bytes)))
+        });
+        group.bench_with_input(BenchmarkId::new("validate", mib), &bytes, |b, bytes| {
+            b.iter(|| validate_save_bytes(black_box(bytes)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("teleport", mib), &bytes, |b, bytes| {
+            let mut bytes = bytes.clone();
+            b.iter(|| teleport_bytes(black_box(&mut bytes), &LOCATIONS[0]).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_scan);
+criterion_main!(benches);
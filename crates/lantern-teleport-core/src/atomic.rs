@@ -0,0 +1,75 @@
+//! Crash-safe file writes
+//!
+//! Save files are never written in place. The new contents go to a temporary
+//! file in the same directory, which is flushed to disk and then renamed over
+//! the original. A crash at any point leaves either the old or the new file,
+//! never a truncated one.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::types::TeleportError;
+
+/// Atomically replace the file at `path` with `bytes`.
+///
+/// The temporary file is removed again if any step before the rename fails.
+pub(crate) fn write_atomic(path: &Path, bytes: &[u8]) -> Result<(), TeleportError> {
+    let temp_path = temp_path_for(path)?;
+
+    if let Err(e) = write_and_sync(&temp_path, bytes) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(TeleportError::WriteError {
+            path: Some(path.to_path_buf()),
+            source: e,
+        });
+    }
+
+    if let Err(e) = fs::rename(&temp_path, path) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(TeleportError::WriteError {
+            path: Some(path.to_path_buf()),
+            source: e,
+        });
+    }
+
+    sync_parent_dir(path);
+
+    Ok(())
+}
+
+/// Write `bytes` to a new file and flush it to disk
+fn write_and_sync(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut file = File::create(path)?;
+    file.write_all(bytes)?;
+    file.sync_all()
+}
+
+/// Build the temporary file path next to `path`
+fn temp_path_for(path: &Path) -> Result<PathBuf, TeleportError> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| TeleportError::InvalidPath(path.to_path_buf()))?;
+
+    let temp_name = format!(
+        ".{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id()
+    );
+    Ok(path.with_file_name(temp_name))
+}
+
+/// Flush the directory entry for the rename to disk.
+///
+/// Directories cannot be opened for syncing on every platform, so this is
+/// best effort: the data itself has already been synced at this point.
+fn sync_parent_dir(path: &Path) {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    if let Ok(dir) = File::open(parent) {
+        let _ = dir.sync_all();
+    }
+}
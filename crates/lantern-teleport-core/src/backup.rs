@@ -0,0 +1,207 @@
+//! Versioned backup store for save files
+//!
+//! Backups are timestamped copies of a save file kept in a directory next to it.
+//! Each save file gets its own set of backups, named `<file name>.<unix millis>.bak`,
+//! and the oldest ones are pruned once the configured retention limit is exceeded.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::atomic;
+use crate::constants;
+use crate::types::{BackupConfig, BackupInfo, CurrentPosition, TeleportError};
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+/// Create a new backup of the given save file.
+///
+/// The backup is written to the configured backup directory, after which
+/// backups exceeding the retention limit are removed (oldest first).
+///
+/// # Arguments
+///
+/// * `save_path` - Path to the Bloodborne save file
+/// * `config` - Where to store backups and how many to keep
+///
+/// # Returns
+///
+/// `Ok(BackupInfo)` describing the new backup, or a `TeleportError` if the
+/// save file could not be copied.
+pub fn create_backup<P: AsRef<Path>>(
+    save_path: P,
+    config: &BackupConfig,
+) -> Result<BackupInfo, TeleportError> {
+    let path = save_path.as_ref();
+    let file_name = save_file_name(path)?;
+    let dir = backup_dir(path, config);
+
+    fs::create_dir_all(&dir).map_err(|source| TeleportError::BackupError {
+        path: dir.clone(),
+        source,
+    })?;
+
+    // Pick a timestamp after the newest backup, so two backups created within
+    // the same millisecond do not overwrite each other and the new one is
+    // never the first to be pruned
+    let mut millis = unix_millis(SystemTime::now());
+    if let Some(newest) = list_backups(path, config)?.first() {
+        millis = millis.max(unix_millis(newest.created) + 1);
+    }
+    let mut backup_path = dir.join(backup_file_name(&file_name, millis));
+    while backup_path.exists() {
+        millis += 1;
+        backup_path = dir.join(backup_file_name(&file_name, millis));
+    }
+
+    let size = fs::copy(path, &backup_path).map_err(|source| TeleportError::BackupError {
+        path: backup_path.clone(),
+        source,
+    })?;
+
+    prune_backups(path, config)?;
+
+    Ok(BackupInfo {
+        path: backup_path,
+        created: UNIX_EPOCH + Duration::from_millis(millis),
+        size,
+    })
+}
+
+/// List all backups of the given save file, newest first.
+///
+/// A missing backup directory is not an error; it simply means no backups
+/// have been created yet.
+pub fn list_backups<P: AsRef<Path>>(
+    save_path: P,
+    config: &BackupConfig,
+) -> Result<Vec<BackupInfo>, TeleportError> {
+    let path = save_path.as_ref();
+    let file_name = save_file_name(path)?;
+    let dir = backup_dir(path, config);
+
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(source) => return Err(TeleportError::BackupError { path: dir, source }),
+    };
+
+    let mut backups: Vec<BackupInfo> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let millis = parse_backup_file_name(&name, &file_name)?;
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            Some(BackupInfo {
+                path: entry.path(),
+                created: UNIX_EPOCH + Duration::from_millis(millis),
+                size,
+            })
+        })
+        .collect();
+
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.created));
+    Ok(backups)
+}
+
+/// Inspect a backup by validating it and extracting the position it holds.
+#[inline]
+pub fn inspect_backup(backup: &BackupInfo) -> Result<CurrentPosition, TeleportError> {
+    crate::validate_save_file(&backup.path)
+}
+
+/// Restore a backup over the given save file.
+///
+/// The backup is validated first, and the current save file is itself backed
+/// up before being replaced, so a restore can always be undone. The save file
+/// is replaced atomically.
+pub fn restore_backup<P: AsRef<Path>>(
+    save_path: P,
+    backup: &BackupInfo,
+    config: &BackupConfig,
+) -> Result<(), TeleportError> {
+    let path = save_path.as_ref();
+
+    inspect_backup(backup)?;
+
+    if config.enabled && path.exists() {
+        create_backup(path, config)?;
+    }
+
+    let bytes = fs::read(&backup.path).map_err(|source| TeleportError::ReadError {
+        path: Some(backup.path.clone()),
+        source,
+    })?;
+    atomic::write_atomic(path, &bytes)?;
+
+    Ok(())
+}
+
+// ============================================================================
+// Private helper functions
+// ============================================================================
+
+/// Remove the oldest backups beyond the configured retention limit
+fn prune_backups(save_path: &Path, config: &BackupConfig) -> Result<(), TeleportError> {
+    let Some(max_backups) = config.max_backups else {
+        return Ok(());
+    };
+
+    for backup in list_backups(save_path, config)?.iter().skip(max_backups) {
+        fs::remove_file(&backup.path).map_err(|source| TeleportError::BackupError {
+            path: backup.path.clone(),
+            source,
+        })?;
+    }
+
+    Ok(())
+}
+
+#[inline]
+pub(crate) fn backup_dir(save_path: &Path, config: &BackupConfig) -> PathBuf {
+    match &config.directory {
+        Some(dir) => dir.clone(),
+        None => save_path
+            .parent()
+            .unwrap_or_else(|| Path::new("."))
+            .join(constants::BACKUP_DIR_NAME),
+    }
+}
+
+#[inline]
+pub(crate) fn save_file_name(save_path: &Path) -> Result<String, TeleportError> {
+    save_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| TeleportError::InvalidPath(save_path.to_path_buf()))
+}
+
+#[inline]
+fn backup_file_name(file_name: &str, millis: u64) -> String {
+    format!(
+        "{}.{}.{}",
+        file_name,
+        millis,
+        constants::BACKUP_FILE_EXTENSION
+    )
+}
+
+/// Parse the timestamp out of a backup file name, if it belongs to `file_name`
+#[inline]
+fn parse_backup_file_name(name: &str, file_name: &str) -> Option<u64> {
+    name.strip_prefix(file_name)?
+        .strip_prefix('.')?
+        .strip_suffix(constants::BACKUP_FILE_EXTENSION)?
+        .strip_suffix('.')?
+        .parse()
+        .ok()
+}
+
+#[inline]
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,220 @@
+//! User bookmarks: named positions captured from save files
+//!
+//! Bookmarks are stored per user in a TOML file (by default in the platform's
+//! config directory) and can be used as teleport destinations just like the
+//! built-in lanterns.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::atomic;
+use crate::constants;
+use crate::journal::{self, JournalAction};
+use crate::map_id::MapId;
+use crate::types::{self, CurrentPosition, TeleportError, TeleportOptions, TeleportOutcome};
+
+/// A named position captured from a save file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    /// Name of the bookmark
+    pub name: String,
+    /// X coordinate
+    pub x: f32,
+    /// Y coordinate
+    pub y: f32,
+    /// Z coordinate
+    pub z: f32,
+    /// Map ID as a 4-byte array (save file format)
+    pub map_id: [u8; 4],
+}
+
+impl Bookmark {
+    /// Create a bookmark from a position extracted from a save file
+    pub fn from_position(name: impl Into<String>, position: &CurrentPosition) -> Self {
+        Self {
+            name: name.into(),
+            x: position.x,
+            y: position.y,
+            z: position.z,
+            map_id: position.map_id,
+        }
+    }
+}
+
+/// On-disk layout of the bookmarks file
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BookmarksFile {
+    #[serde(default, rename = "bookmark")]
+    bookmarks: Vec<Bookmark>,
+}
+
+/// A collection of bookmarks backed by a TOML file
+#[derive(Debug, Clone)]
+pub struct BookmarkStore {
+    path: PathBuf,
+    bookmarks: Vec<Bookmark>,
+}
+
+impl BookmarkStore {
+    /// Default location of the per-user bookmarks file, if the platform has
+    /// a config directory
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| {
+            dir.join(constants::CONFIG_DIR_NAME)
+                .join(constants::BOOKMARKS_FILE_NAME)
+        })
+    }
+
+    /// Load the per-user bookmarks file from its default location
+    pub fn load_default() -> Result<Self, TeleportError> {
+        let path = Self::default_path().ok_or_else(|| TeleportError::BookmarkError {
+            path: None,
+            message: "no config directory available".to_string(),
+            source: None,
+        })?;
+        Self::load(path)
+    }
+
+    /// Load bookmarks from the given file.
+    ///
+    /// A missing file is not an error; it yields an empty store that will be
+    /// created on the first [`BookmarkStore::save`].
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, TeleportError> {
+        let path = path.as_ref();
+
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                return Err(TeleportError::BookmarkError {
+                    path: Some(path.to_path_buf()),
+                    message: "cannot read the file".to_string(),
+                    source: Some(Box::new(e)),
+                });
+            }
+        };
+
+        let file: BookmarksFile =
+            toml::from_str(&contents).map_err(|e| TeleportError::BookmarkError {
+                path: Some(path.to_path_buf()),
+                message: "invalid TOML".to_string(),
+                source: Some(Box::new(e)),
+            })?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            bookmarks: file.bookmarks,
+        })
+    }
+
+    /// Path of the bookmarks file
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// All bookmarks, in the order they were added
+    #[inline]
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    /// Find a bookmark by name (case-insensitive)
+    pub fn get(&self, name: &str) -> Option<&Bookmark> {
+        self.bookmarks
+            .iter()
+            .find(|b| b.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Add a bookmark.
+    ///
+    /// Fails with `TeleportError::BookmarkExists` if a bookmark with the same
+    /// name (case-insensitive) already exists.
+    pub fn add(&mut self, bookmark: Bookmark) -> Result<(), TeleportError> {
+        if bookmark.name.trim().is_empty() {
+            return Err(TeleportError::BookmarkError {
+                path: None,
+                message: "bookmark name cannot be empty".to_string(),
+                source: None,
+            });
+        }
+        if self.get(&bookmark.name).is_some() {
+            return Err(TeleportError::BookmarkExists(bookmark.name));
+        }
+
+        self.bookmarks.push(bookmark);
+        Ok(())
+    }
+
+    /// Remove a bookmark by name (case-insensitive) and return it
+    pub fn remove(&mut self, name: &str) -> Result<Bookmark, TeleportError> {
+        let index = self
+            .bookmarks
+            .iter()
+            .position(|b| b.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| TeleportError::BookmarkNotFound(name.to_string()))?;
+
+        Ok(self.bookmarks.remove(index))
+    }
+
+    /// Write the bookmarks back to their file, creating its directory if needed
+    pub fn save(&self) -> Result<(), TeleportError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(|e| TeleportError::BookmarkError {
+                path: Some(parent.to_path_buf()),
+                message: "cannot create the directory".to_string(),
+                source: Some(Box::new(e)),
+            })?;
+        }
+
+        let file = BookmarksFile {
+            bookmarks: self.bookmarks.clone(),
+        };
+        let contents = toml::to_string(&file).map_err(|e| TeleportError::BookmarkError {
+            path: Some(self.path.clone()),
+            message: "cannot serialize the bookmarks".to_string(),
+            source: Some(Box::new(e)),
+        })?;
+
+        atomic::write_atomic(&self.path, contents.as_bytes())
+    }
+}
+
+/// Teleport to a bookmarked position in a Bloodborne save file.
+///
+/// Behaves like [`crate::teleport_with_options`], with the bookmark's
+/// coordinates and map ID as the destination. Like [`crate::Location::custom`],
+/// it fails with `TeleportError::NonFiniteCoordinate`,
+/// `TeleportError::UnknownMapId` or `TeleportError::InvalidMapId` before
+/// touching the save if the bookmark is not a valid destination.
+///
+/// # Arguments
+///
+/// * `save_path` - Path to the Bloodborne save file
+/// * `bookmark` - The destination bookmark
+/// * `options` - Options controlling backups
+pub fn teleport_to_bookmark<P: AsRef<Path>>(
+    save_path: P,
+    bookmark: &Bookmark,
+    options: &TeleportOptions,
+) -> Result<TeleportOutcome, TeleportError> {
+    // Bookmark files can be edited by hand, so check the destination again
+    types::ensure_finite(bookmark.x, bookmark.y, bookmark.z)?;
+    MapId::from_save_format(bookmark.map_id)?;
+
+    journal::apply_edit(
+        save_path.as_ref(),
+        options,
+        JournalAction::Teleport,
+        &bookmark.name,
+        |save| {
+            save.set_map_id(bookmark.map_id)?;
+            save.set_position(bookmark.x, bookmark.y, bookmark.z)?;
+            let map_id = constants::map_ids::from_save_format(&bookmark.map_id);
+            Ok(((), save.destination_warnings(&map_id)))
+        },
+    )
+    .map(|((), outcome)| outcome)
+}
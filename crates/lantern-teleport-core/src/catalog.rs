@@ -0,0 +1,290 @@
+//! Location catalog: the built-in lanterns merged with external catalog files
+//!
+//! Catalog files are TOML or JSON documents with a list of `locations`, each
+//! with a name, region, coordinates and map ID. A merged catalog is built once
+//! and installed for the whole process, after which [`crate::get_all_locations`]
+//! and [`crate::search_locations`] search it instead of the built-in set.
+//!
+//! ```toml
+//! [[locations]]
+//! name = "Cleric Beast Fog Gate"
+//! region = "Yharnam Headstone"
+//! x = -160.0
+//! y = -25.0
+//! z = 60.0
+//! map_id = [0x01, 0x18]
+//! ```
+
+#[cfg(feature = "user-files")]
+use std::fs;
+#[cfg(feature = "user-files")]
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+#[cfg(feature = "user-files")]
+use serde::Deserialize;
+
+use crate::constants;
+use crate::types::{Location, OwnedLocation, Region, TeleportError};
+
+/// The catalog installed for this process, if any
+static INSTALLED: OnceLock<&'static [Location]> = OnceLock::new();
+
+/// On-disk layout of a catalog file
+#[cfg(feature = "user-files")]
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CatalogFile {
+    #[serde(default)]
+    locations: Vec<OwnedLocation>,
+}
+
+/// A set of teleport locations built from the built-in lanterns and any
+/// number of catalog files
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    locations: Vec<Location>,
+}
+
+impl Catalog {
+    /// A catalog containing only the built-in lanterns
+    pub fn builtin() -> Self {
+        Self {
+            locations: constants::LOCATIONS.to_vec(),
+        }
+    }
+
+    /// All locations in the catalog
+    #[inline]
+    pub fn locations(&self) -> &[Location] {
+        &self.locations
+    }
+
+    /// Install this catalog for the whole process.
+    ///
+    /// From then on, [`crate::get_all_locations`] and [`crate::search_locations`]
+    /// use it instead of the built-in lanterns. A catalog can only be installed
+    /// once; later attempts fail with `TeleportError::CatalogError`.
+    pub fn install(self) -> Result<(), TeleportError> {
+        let locations: &'static [Location] = Box::leak(self.locations.into_boxed_slice());
+        INSTALLED
+            .set(locations)
+            .map_err(|_| TeleportError::CatalogError {
+                path: None,
+                message: "a catalog is already installed".to_string(),
+                source: None,
+            })
+    }
+}
+
+#[cfg(feature = "user-files")]
+impl Catalog {
+    /// Directory searched for per-user catalog files, if the platform has a
+    /// config directory
+    pub fn user_catalog_dir() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| {
+            dir.join(constants::CONFIG_DIR_NAME)
+                .join(constants::CATALOG_DIR_NAME)
+        })
+    }
+
+    /// Merge every `.toml` and `.json` file in the per-user catalog directory.
+    ///
+    /// Files are merged in file name order. A missing directory is not an error.
+    pub fn load_user_catalogs(&mut self) -> Result<(), TeleportError> {
+        match Self::user_catalog_dir() {
+            Some(dir) => self.load_dir(dir),
+            None => Ok(()),
+        }
+    }
+
+    /// Merge every `.toml` and `.json` file in the given directory, in file
+    /// name order. A missing directory is not an error.
+    pub fn load_dir<P: AsRef<Path>>(&mut self, dir: P) -> Result<(), TeleportError> {
+        let dir = dir.as_ref();
+
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => {
+                return Err(TeleportError::CatalogError {
+                    path: Some(dir.to_path_buf()),
+                    message: "cannot read the directory".to_string(),
+                    source: Some(Box::new(e)),
+                });
+            }
+        };
+
+        let mut paths: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| CatalogFormat::from_path(path).is_some())
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            self.load_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// Merge a single TOML or JSON catalog file.
+    ///
+    /// Every entry is validated before anything is merged, so a file with an
+    /// error leaves the catalog untouched.
+    ///
+    /// # Errors
+    ///
+    /// * `TeleportError::CatalogError` if the file cannot be read or parsed, or
+    ///   an entry has an empty name/region, non-finite coordinates or an
+    ///   unknown map ID
+    /// * `TeleportError::DuplicateLocation` if an entry has the same name
+    ///   (case-insensitive) as a location already in the catalog
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), TeleportError> {
+        let path = path.as_ref();
+        let error = |message: String, source: Option<Box<dyn std::error::Error + Send + Sync>>| {
+            TeleportError::CatalogError {
+                path: Some(path.to_path_buf()),
+                message,
+                source,
+            }
+        };
+
+        let format = CatalogFormat::from_path(path)
+            .ok_or_else(|| error("expected a .toml or .json file".to_string(), None))?;
+        let contents = fs::read_to_string(path)
+            .map_err(|e| error("cannot read the file".to_string(), Some(Box::new(e))))?;
+
+        let file: CatalogFile = match format {
+            CatalogFormat::Toml => toml::from_str(&contents)
+                .map_err(|e| error("invalid TOML".to_string(), Some(Box::new(e))))?,
+            CatalogFormat::Json => serde_json::from_str(&contents)
+                .map_err(|e| error("invalid JSON".to_string(), Some(Box::new(e))))?,
+        };
+
+        let mut new_locations: Vec<Location> = Vec::with_capacity(file.locations.len());
+        for entry in file.locations {
+            validate_entry(&entry)
+                .map_err(|message| error(format!("'{}': {}", entry.name, message), None))?;
+
+            let duplicate = self
+                .locations
+                .iter()
+                .chain(new_locations.iter())
+                .any(|loc| loc.name.eq_ignore_ascii_case(&entry.name));
+            if duplicate {
+                return Err(TeleportError::DuplicateLocation(entry.name));
+            }
+
+            new_locations.push(Location {
+                name: leak(entry.name),
+                region: leak(entry.region),
+                x: entry.x,
+                y: entry.y,
+                z: entry.z,
+                map_id: entry.map_id,
+                facing: entry.facing,
+            });
+        }
+
+        self.locations.extend(new_locations);
+        Ok(())
+    }
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+/// The installed catalog, or the built-in lanterns if none was installed
+#[inline]
+pub(crate) fn installed_locations() -> &'static [Location] {
+    INSTALLED.get().copied().unwrap_or(&constants::LOCATIONS)
+}
+
+/// Build owned regions from locations, keeping the order in which regions
+/// first appear
+pub(crate) fn to_regions(locations: &[Location]) -> Vec<Region> {
+    group_by_region(locations)
+        .into_iter()
+        .map(|(name, locations)| Region {
+            name: name.to_string(),
+            locations: locations.into_iter().map(OwnedLocation::from).collect(),
+        })
+        .collect()
+}
+
+/// Group locations by region, keeping the order in which regions first appear
+pub(crate) fn group_by_region(locations: &[Location]) -> Vec<(&str, Vec<&Location>)> {
+    let mut regions: Vec<(&str, Vec<&Location>)> = Vec::new();
+    for location in locations {
+        if let Some(existing) = regions
+            .iter_mut()
+            .find(|(name, _)| *name == location.region)
+        {
+            existing.1.push(location);
+        } else {
+            regions.push((location.region, vec![location]));
+        }
+    }
+    regions
+}
+
+// ============================================================================
+// Private helper functions
+// ============================================================================
+
+/// Supported catalog file formats
+#[cfg(feature = "user-files")]
+enum CatalogFormat {
+    Toml,
+    Json,
+}
+
+#[cfg(feature = "user-files")]
+impl CatalogFormat {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Check an entry against the catalog schema
+#[cfg(feature = "user-files")]
+fn validate_entry(entry: &OwnedLocation) -> Result<(), String> {
+    if entry.name.trim().is_empty() {
+        return Err("name cannot be empty".to_string());
+    }
+    if entry.region.trim().is_empty() {
+        return Err("region cannot be empty".to_string());
+    }
+    for (axis, value) in [('x', entry.x), ('y', entry.y), ('z', entry.z)] {
+        if !value.is_finite() {
+            return Err(format!("{} coordinate must be finite", axis));
+        }
+    }
+    if entry.facing.is_some_and(|facing| !facing.is_finite()) {
+        return Err("facing must be finite".to_string());
+    }
+    if !constants::map_ids::is_known(&entry.map_id) {
+        return Err(format!(
+            "unknown map ID {:02X}:{:02X}",
+            entry.map_id[0], entry.map_id[1]
+        ));
+    }
+    Ok(())
+}
+
+/// Give a loaded string the same lifetime as the built-in catalog.
+///
+/// Catalogs are loaded once per process, so the leak is bounded.
+#[cfg(feature = "user-files")]
+#[inline]
+fn leak(s: String) -> &'static str {
+    Box::leak(s.into_boxed_str())
+}
@@ -14,7 +14,166 @@ const COORD_PATTERN: [u8; 12] = [
 
 const COORD_OFFSET_AFTER_PATTERN: usize = 12;
 
+// ============================================================================
+// Save layout constants
+// ============================================================================
+
+/// Offset of the 4-byte map ID from the start of the save file
+pub(crate) const MAP_ID_OFFSET: usize = 0x04;
+
+/// Length of the X/Y/Z coordinate block (three little-endian f32 values)
+pub(crate) const COORD_BLOCK_LEN: usize = 12;
+
+/// Offset of the character's facing from the start of the coordinate block.
+///
+/// Assumed to be a yaw in radians (little-endian f32) directly after Z. This
+/// is not taken from a documented save layout and has not been checked
+/// against real saves, so the built-in locations leave their facing unset and
+/// the facing is only written when one is asked for explicitly.
+pub(crate) const FACING_OFFSET_AFTER_COORDS: usize = COORD_BLOCK_LEN;
+
+/// Smallest file that can hold the LCED marker, coordinate pattern and block
+pub(crate) const MIN_SAVE_LEN: usize =
+    LCED_MARKER.len() + COORD_OFFSET_AFTER_PATTERN + COORD_BLOCK_LEN;
+
+/// A file without an LCED marker in which fewer than one byte in this many is
+/// zero is reported as encrypted. Decrypted saves are mostly zero padding,
+/// while encrypted data has about one zero byte in 256.
+pub(crate) const ENCRYPTED_ZERO_BYTE_RATIO: usize = 64;
+
+/// How far (in game units) outside the range spanned by the known locations a
+/// coordinate may lie and still count as plausible
+pub(crate) const PLAUSIBLE_COORD_MARGIN: f32 = 500.0;
+
+// ============================================================================
+// Character block constants
+// ============================================================================
+
+// Offsets of the character fields, relative to the LCED marker. Numbers are
+// little-endian u32 values; the name is UTF-16LE, padded with NULs. These
+// offsets are unverified: they have not been checked against real saves, so
+// the fields are only ever read, and values the game cannot produce are
+// dropped.
+
+/// Offset of the play time, in milliseconds
+pub(crate) const PLAYTIME_OFFSET: usize = 0x04;
+
+/// Offset of the character level
+pub(crate) const LEVEL_OFFSET: usize = 0x08;
+
+/// Offset of the insight count
+pub(crate) const INSIGHT_OFFSET: usize = 0x0C;
+
+/// Offset of the blood echoes held
+pub(crate) const BLOOD_ECHOES_OFFSET: usize = 0x10;
+
+/// Offset of the NG+ cycle (0 for the first playthrough)
+pub(crate) const NG_CYCLE_OFFSET: usize = 0x14;
+
+/// Offset of the character name
+pub(crate) const NAME_OFFSET: usize = 0x18;
+
+/// Maximum length of the character name, in UTF-16 code units
+pub(crate) const NAME_MAX_UNITS: usize = 16;
+
+// Values outside these ranges are treated as unreadable rather than shown
+
+/// Highest character level the game allows
+pub(crate) const MAX_LEVEL: u32 = 544;
+
+/// Highest insight count the game allows
+pub(crate) const MAX_INSIGHT: u32 = 999;
+
+/// Highest number of blood echoes the game allows
+pub(crate) const MAX_BLOOD_ECHOES: u32 = 999_999_999;
+
+/// Highest NG+ cycle accepted as plausible
+pub(crate) const MAX_NG_CYCLE: u32 = 7;
+
+/// Longest play time accepted as plausible (999:59:59), in milliseconds
+pub(crate) const MAX_PLAYTIME_MS: u32 = ((999 * 60 + 59) * 60 + 59) * 1000;
+
+// ============================================================================
+// Event flag constants
+// ============================================================================
+
+/// Offset of the lantern event flags, relative to the LCED marker. Flag `n`
+/// is bit `7 - n % 8` of byte `n / 8` (most significant bit first).
+///
+/// This layout is an assumption that has not been checked against real
+/// saves, so the flags are only ever read, never written.
+pub(crate) const LANTERN_FLAGS_OFFSET: usize = 0x38;
+
+/// Length of the lantern event flag block, in bytes
+pub(crate) const LANTERN_FLAGS_LEN: usize = 8;
+
+/// Event flag set once the character has entered the Hunter's Nightmare
+/// (The Old Hunters DLC), numbered like the lantern flags. Like the lantern
+/// flag layout, this bit position has not been checked against real saves.
+pub(crate) const DLC_ENTRY_FLAG: u32 = 39;
+
+/// Event flag set once night falls on Yharnam, numbered like the lantern flags.
+/// Not checked against real saves, like [`BLOOD_MOON_FLAG`].
+#[cfg(feature = "experimental")]
+pub(crate) const NIGHT_FLAG: u32 = 56;
+
+/// Event flag set once the Blood Moon has risen, numbered like the lantern
+/// flags. Not checked against real saves.
+#[cfg(feature = "experimental")]
+pub(crate) const BLOOD_MOON_FLAG: u32 = 57;
+
+// ============================================================================
+// Backup constants
+// ============================================================================
+
+/// Name of the backup directory created next to the save file
+pub(crate) const BACKUP_DIR_NAME: &str = "lantern-teleport-backups";
+
+/// File extension used for backup files
+pub(crate) const BACKUP_FILE_EXTENSION: &str = "bak";
+
+/// File extension of the teleport journal kept next to the backups
+pub(crate) const JOURNAL_FILE_EXTENSION: &str = "journal";
+
+/// Offset basis of the 64-bit FNV-1a hash used to detect changed save files
+pub(crate) const FNV_OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+
+/// Prime of the 64-bit FNV-1a hash
+pub(crate) const FNV_PRIME: u64 = 0x0000_0100_0000_01B3;
+
+// ============================================================================
+// User configuration constants
+// ============================================================================
+
+/// Name of the per-user config directory
+#[cfg(feature = "user-files")]
+pub(crate) const CONFIG_DIR_NAME: &str = "lantern-teleport";
+
+/// File name of the per-user bookmarks file
+#[cfg(feature = "user-files")]
+pub(crate) const BOOKMARKS_FILE_NAME: &str = "bookmarks.toml";
+
+/// Name of the per-user directory holding extra catalog files
+#[cfg(feature = "user-files")]
+pub(crate) const CATALOG_DIR_NAME: &str = "catalogs";
+
+/// Name used for locations built from arbitrary coordinates
+pub(crate) const CUSTOM_LOCATION_NAME: &str = "Custom Coordinates";
+
+/// Region used for locations built from arbitrary coordinates
+pub(crate) const CUSTOM_LOCATION_REGION: &str = "Custom";
+
+/// File name prefix of character save files (`userdata0000`, ...)
+pub(crate) const SLOT_FILE_PREFIX: &str = "userdata";
+
+/// Number of digits in a character save file's slot index
+pub(crate) const SLOT_INDEX_DIGITS: usize = 4;
+
+/// Number of backups kept per save file by default
+pub(crate) const DEFAULT_MAX_BACKUPS: usize = 10;
+
 pub(crate) mod map_ids {
+    use crate::types::WorldState;
 
     // Hunter's Dream
     pub const HUNTERS_DREAM: [u8; 2] = [0x00, 0x15];
@@ -45,17 +204,150 @@ pub(crate) mod map_ids {
     pub const RESEARCH_HALL_0: [u8; 2] = [0x00, 0x23];
     pub const FISHING_HAMLET_0: [u8; 2] = [0x00, 0x24];
 
+    /// Every map ID used by the location catalog
+    pub const KNOWN: [[u8; 2]; 17] = [
+        HUNTERS_DREAM,
+        CENTRAL_YHARNAM_1,
+        CENTRAL_YHARNAM_0,
+        CENTRAL_YHARNAM_2,
+        OLD_YHARNAM_0,
+        HEMWICK_0,
+        FORBIDDEN_WOODS_0,
+        BYRGENWERTH_0,
+        BYRGENWERTH_2,
+        YAHARGUL_0,
+        CAINHURST_0,
+        ABANDONED_WORKSHOP_1,
+        NIGHTMARE_FRONTIER_0,
+        MERGOS_LOFT_0,
+        HUNTERS_NIGHTMARE_0,
+        RESEARCH_HALL_0,
+        FISHING_HAMLET_0,
+    ];
+
+    /// Check whether a map ID is one of the known map IDs
+    pub fn is_known(map_id: &[u8; 2]) -> bool {
+        KNOWN.contains(map_id)
+    }
+
+    /// Names of the map variants that are named apart from their area. Every
+    /// other variant goes by the name of its area (see `MapArea::name`).
+    const VARIANT_NAMES: [([u8; 2], &str); 5] = [
+        (CENTRAL_YHARNAM_1, "Central Yharnam"),
+        (CENTRAL_YHARNAM_0, "Cathedral Ward"),
+        (CENTRAL_YHARNAM_2, "Upper Cathedral Ward"),
+        (BYRGENWERTH_2, "Moonside Lake"),
+        (ABANDONED_WORKSHOP_1, "Abandoned Old Workshop"),
+    ];
+
+    /// Look up the name of a map variant that is named apart from its area
+    pub fn variant_name(map_id: &[u8; 2]) -> Option<&'static str> {
+        VARIANT_NAMES
+            .iter()
+            .find(|(id, _)| id == map_id)
+            .map(|(_, name)| *name)
+    }
+
+    /// Earliest world state in which a map variant exists. Variants that are
+    /// separate parts of an area rather than story states (such as Central
+    /// Yharnam, Cathedral Ward and Upper Cathedral Ward) are not listed and
+    /// always exist.
+    const WORLD_STATE_VARIANTS: [([u8; 2], WorldState); 1] =
+        [(BYRGENWERTH_2, WorldState::BloodMoon)];
+
+    /// Look up the earliest world state in which a map variant exists
+    pub fn required_world_state(map_id: &[u8; 2]) -> WorldState {
+        WORLD_STATE_VARIANTS
+            .iter()
+            .find(|(id, _)| id == map_id)
+            .map_or(WorldState::Evening, |(_, state)| *state)
+    }
+
+    /// Convert a map ID from the 4-byte save file format
+    pub fn from_save_format(map_id: &[u8; 4]) -> [u8; 2] {
+        [map_id[2], map_id[3]]
+    }
+
     /// Convert a map ID to the 4-byte format used in save files
     pub fn to_save_format(map_id: &[u8; 2]) -> [u8; 4] {
         [0x00, 0x00, map_id[0], map_id[1]]
     }
 }
 
+/// Event flag lit by each lantern of the built-in catalog, numbered within
+/// the lantern flag block and grouped by headstone
+pub(crate) mod lantern_flags {
+    /// Lantern name and event flag
+    pub const FLAGS: [(&str, u32); 44] = [
+        // Hunter's Dream
+        ("Hunter's Dream", 0),
+        // Yharnam Headstone
+        ("1st Floor Sickroom", 1),
+        ("Central Yharnam", 2),
+        ("Great Bridge", 3),
+        ("Tomb of Oedon", 4),
+        ("Cathedral Ward", 5),
+        ("Grand Cathedral Ward", 6),
+        ("Upper Cathedral Ward", 7),
+        ("Lumenflower Gardens", 8),
+        ("Altar of Despair", 9),
+        ("Old Yharnam", 10),
+        ("Church of the Good Chalice", 11),
+        ("Graveyard of the Darkbeast", 12),
+        // Frontier Headstone
+        ("Hemwick Charnel Lane", 16),
+        ("Witch's Abode", 17),
+        ("Forbidden Woods", 18),
+        ("Forbidden Grave", 19),
+        ("Byrgenwerth", 20),
+        ("Moonside Lake", 21),
+        // Unseen Headstone
+        ("Yahar'gul, Unseen Village", 24),
+        ("Yahar'gul Chapel", 25),
+        ("Advent Plaza", 26),
+        ("Hypogean Gaol", 27),
+        ("Forsaken Castle Cainhurst", 28),
+        ("Logarius' Seat", 29),
+        ("Vileblood Queen's Chamber", 30),
+        ("Abandoned Old Workshop", 31),
+        // Nightmare Headstone
+        ("Lecture Building", 32),
+        ("Lecture Building 2nd Floor", 33),
+        ("Nightmare Frontier", 34),
+        ("Nightmare of Mensis", 35),
+        ("Mergo's Loft: Base", 36),
+        ("Mergo's Loft: Middle", 37),
+        ("Wet Nurse's Lunarium", 38),
+        // Hunter's Nightmare Headstone
+        ("Hunter's Nightmare", 40),
+        ("Nightmare Church", 41),
+        ("Nightmare Grand Cathedral", 42),
+        ("Underground Corpse Pile", 43),
+        ("Research Hall", 44),
+        ("Lumenwood Garden", 45),
+        ("Astral Clocktower", 46),
+        ("Fishing Hamlet", 47),
+        ("Lighthouse Hut", 48),
+        ("Coast", 49),
+    ];
+
+    /// Look up the event flag of a lantern by name
+    pub fn flag_of(name: &str) -> Option<u32> {
+        FLAGS
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, flag)| *flag)
+    }
+}
+
 // ============================================================================
 // Location data - all available teleport locations
 // ============================================================================
 
-/// All available lantern teleport locations
+/// All available lantern teleport locations.
+///
+/// None of them sets a facing until the facing field is confirmed; see
+/// [`FACING_OFFSET_AFTER_COORDS`].
 pub const LOCATIONS: [Location; 44] = [
     // Hunter's Dream
     Location {
@@ -65,6 +357,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -6.0,
         z: -18.0,
         map_id: map_ids::HUNTERS_DREAM,
+        facing: None,
     },
     // Yharnam Headstone
     Location {
@@ -74,6 +367,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -50.759,
         z: 179.42,
         map_id: map_ids::CENTRAL_YHARNAM_1,
+        facing: None,
     },
     Location {
         name: "Central Yharnam",
@@ -82,6 +376,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -28.646,
         z: 68.5,
         map_id: map_ids::CENTRAL_YHARNAM_1,
+        facing: None,
     },
     Location {
         name: "Great Bridge",
@@ -90,6 +385,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -27.021,
         z: 64.673,
         map_id: map_ids::CENTRAL_YHARNAM_1,
+        facing: None,
     },
     Location {
         name: "Tomb of Oedon",
@@ -98,6 +394,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -40.722,
         z: 87.303,
         map_id: map_ids::CENTRAL_YHARNAM_1,
+        facing: None,
     },
     Location {
         name: "Cathedral Ward",
@@ -106,6 +403,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -9.511,
         z: 103.27,
         map_id: map_ids::CENTRAL_YHARNAM_0,
+        facing: None,
     },
     Location {
         name: "Grand Cathedral Ward",
@@ -114,6 +412,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 35.713,
         z: 339.689,
         map_id: map_ids::CENTRAL_YHARNAM_0,
+        facing: None,
     },
     Location {
         name: "Upper Cathedral Ward",
@@ -122,6 +421,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 40.621,
         z: 250.57,
         map_id: map_ids::CENTRAL_YHARNAM_2,
+        facing: None,
     },
     Location {
         name: "Lumenflower Gardens",
@@ -130,6 +430,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 51.403,
         z: 300.35,
         map_id: map_ids::CENTRAL_YHARNAM_2,
+        facing: None,
     },
     Location {
         name: "Altar of Despair",
@@ -138,6 +439,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 4.443,
         z: 425.02,
         map_id: map_ids::CENTRAL_YHARNAM_2,
+        facing: None,
     },
     Location {
         name: "Old Yharnam",
@@ -146,6 +448,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -65.214,
         z: 36.0,
         map_id: map_ids::OLD_YHARNAM_0,
+        facing: None,
     },
     Location {
         name: "Church of the Good Chalice",
@@ -154,6 +457,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -126.664,
         z: 57.359,
         map_id: map_ids::OLD_YHARNAM_0,
+        facing: None,
     },
     Location {
         name: "Graveyard of the Darkbeast",
@@ -162,6 +466,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -120.783,
         z: -65.249,
         map_id: map_ids::OLD_YHARNAM_0,
+        facing: None,
     },
     // Frontier Headstone
     Location {
@@ -171,6 +476,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -22.0,
         z: 485.5,
         map_id: map_ids::HEMWICK_0,
+        facing: None,
     },
     Location {
         name: "Witch's Abode",
@@ -179,6 +485,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 2.4,
         z: 733.0,
         map_id: map_ids::HEMWICK_0,
+        facing: None,
     },
     Location {
         name: "Forbidden Woods",
@@ -187,6 +494,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -76.3,
         z: 252.0,
         map_id: map_ids::FORBIDDEN_WOODS_0,
+        facing: None,
     },
     Location {
         name: "Forbidden Grave",
@@ -195,6 +503,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -186.5,
         z: 479.0,
         map_id: map_ids::FORBIDDEN_WOODS_0,
+        facing: None,
     },
     Location {
         name: "Byrgenwerth",
@@ -203,6 +512,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -180.8,
         z: 414.6,
         map_id: map_ids::BYRGENWERTH_0,
+        facing: None,
     },
     Location {
         name: "Moonside Lake",
@@ -211,6 +521,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -271.0,
         z: 371.0,
         map_id: map_ids::BYRGENWERTH_2,
+        facing: None,
     },
     // Unseen Headstone
     Location {
@@ -220,6 +531,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -51.4,
         z: 70.0,
         map_id: map_ids::YAHARGUL_0,
+        facing: None,
     },
     Location {
         name: "Yahar'gul Chapel",
@@ -228,6 +540,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -88.0,
         z: -55.6,
         map_id: map_ids::YAHARGUL_0,
+        facing: None,
     },
     Location {
         name: "Advent Plaza",
@@ -236,6 +549,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -123.6,
         z: -253.4,
         map_id: map_ids::YAHARGUL_0,
+        facing: None,
     },
     Location {
         name: "Hypogean Gaol",
@@ -244,6 +558,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -97.6,
         z: -78.8,
         map_id: map_ids::YAHARGUL_0,
+        facing: None,
     },
     Location {
         name: "Forsaken Castle Cainhurst",
@@ -252,6 +567,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 33.8,
         z: -187.9,
         map_id: map_ids::CAINHURST_0,
+        facing: None,
     },
     Location {
         name: "Logarius' Seat",
@@ -260,6 +576,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 111.8,
         z: -350.4,
         map_id: map_ids::CAINHURST_0,
+        facing: None,
     },
     Location {
         name: "Vileblood Queen's Chamber",
@@ -268,6 +585,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 129.0,
         z: -455.0,
         map_id: map_ids::CAINHURST_0,
+        facing: None,
     },
     Location {
         name: "Abandoned Old Workshop",
@@ -276,6 +594,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -19.9,
         z: 140.8,
         map_id: map_ids::ABANDONED_WORKSHOP_1,
+        facing: None,
     },
     // Nightmare Headstone
     Location {
@@ -285,6 +604,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -185.25,
         z: 594.9,
         map_id: map_ids::BYRGENWERTH_0,
+        facing: None,
     },
     Location {
         name: "Lecture Building 2nd Floor",
@@ -293,6 +613,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: -177.25,
         z: 514.19,
         map_id: map_ids::BYRGENWERTH_0,
+        facing: None,
     },
     Location {
         name: "Nightmare Frontier",
@@ -301,6 +622,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 1500.0,
         z: 0.0,
         map_id: map_ids::NIGHTMARE_FRONTIER_0,
+        facing: None,
     },
     Location {
         name: "Nightmare of Mensis",
@@ -309,6 +631,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 1462.28,
         z: -42.65,
         map_id: map_ids::NIGHTMARE_FRONTIER_0,
+        facing: None,
     },
     Location {
         name: "Mergo's Loft: Base",
@@ -317,6 +640,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 986.7,
         z: -0.37,
         map_id: map_ids::MERGOS_LOFT_0,
+        facing: None,
     },
     Location {
         name: "Mergo's Loft: Middle",
@@ -325,6 +649,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 1061.26,
         z: -14.86,
         map_id: map_ids::MERGOS_LOFT_0,
+        facing: None,
     },
     Location {
         name: "Wet Nurse's Lunarium",
@@ -333,6 +658,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 1124.3,
         z: -37.98,
         map_id: map_ids::MERGOS_LOFT_0,
+        facing: None,
     },
     // Hunter's Nightmare Headstone
     Location {
@@ -342,6 +668,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 1490.49,
         z: -497.73,
         map_id: map_ids::HUNTERS_NIGHTMARE_0,
+        facing: None,
     },
     Location {
         name: "Nightmare Church",
@@ -350,6 +677,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 1503.18,
         z: -594.52,
         map_id: map_ids::HUNTERS_NIGHTMARE_0,
+        facing: None,
     },
     Location {
         name: "Nightmare Grand Cathedral",
@@ -358,6 +686,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 1535.71,
         z: -261.57,
         map_id: map_ids::HUNTERS_NIGHTMARE_0,
+        facing: None,
     },
     Location {
         name: "Underground Corpse Pile",
@@ -366,6 +695,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 1503.79,
         z: -743.0,
         map_id: map_ids::HUNTERS_NIGHTMARE_0,
+        facing: None,
     },
     Location {
         name: "Research Hall",
@@ -374,6 +704,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 1553.02,
         z: -824.22,
         map_id: map_ids::RESEARCH_HALL_0,
+        facing: None,
     },
     Location {
         name: "Lumenwood Garden",
@@ -382,6 +713,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 1593.0,
         z: -824.37,
         map_id: map_ids::RESEARCH_HALL_0,
+        facing: None,
     },
     Location {
         name: "Astral Clocktower",
@@ -390,6 +722,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 1595.57,
         z: -824.44,
         map_id: map_ids::RESEARCH_HALL_0,
+        facing: None,
     },
     Location {
         name: "Fishing Hamlet",
@@ -398,6 +731,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 1594.3,
         z: -817.2,
         map_id: map_ids::FISHING_HAMLET_0,
+        facing: None,
     },
     Location {
         name: "Lighthouse Hut",
@@ -406,6 +740,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 1614.66,
         z: -867.2,
         map_id: map_ids::FISHING_HAMLET_0,
+        facing: None,
     },
     Location {
         name: "Coast",
@@ -414,6 +749,7 @@ pub const LOCATIONS: [Location; 44] = [
         y: 1577.27,
         z: -943.2,
         map_id: map_ids::FISHING_HAMLET_0,
+        facing: None,
     },
 ];
 
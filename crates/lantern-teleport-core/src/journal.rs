@@ -0,0 +1,436 @@
+//! Append-only journal of the teleports made to each save file
+//!
+//! Every teleport appends a line to `<file name>.journal` in the save file's
+//! backup directory, recording the position and map ID before and after it,
+//! the bytes it overwrote and a hash of the file on both sides. The journal
+//! lets the last teleport be undone, as long as nothing else has changed the
+//! file since.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::backup;
+use crate::constants;
+use crate::save_file::SaveFile;
+use crate::types::{
+    BackupConfig, CurrentPosition, TeleportError, TeleportOptions, TeleportOutcome, TeleportWarning,
+};
+
+/// What a journal entry records
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JournalAction {
+    /// The character was teleported
+    Teleport,
+    /// An earlier teleport was undone
+    Undo,
+}
+
+/// One edit of a save file recorded in its journal
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalEntry {
+    /// When the edit was made
+    pub time: SystemTime,
+    /// Whether this was a teleport or an undo
+    pub action: JournalAction,
+    /// Name of the destination (for undos, of the teleport that was undone)
+    pub target: String,
+    /// Position and map ID before the edit
+    pub from: CurrentPosition,
+    /// Position and map ID after the edit
+    pub to: CurrentPosition,
+    /// Offset and previous contents of every byte range the edit changed
+    pub overwritten: Vec<(usize, Vec<u8>)>,
+    /// Hash of the save file before the edit
+    pub hash_before: u64,
+    /// Hash of the save file written by the edit
+    pub hash_after: u64,
+}
+
+// ============================================================================
+// Public API
+// ============================================================================
+
+/// Path of the journal of a save file.
+///
+/// The journal lives in the backup directory of the save file (see
+/// [`BackupConfig::directory`]), whether or not backups are enabled.
+pub fn journal_path<P: AsRef<Path>>(
+    save_path: P,
+    config: &BackupConfig,
+) -> Result<PathBuf, TeleportError> {
+    let path = save_path.as_ref();
+    let file_name = backup::save_file_name(path)?;
+    Ok(backup::backup_dir(path, config).join(format!(
+        "{}.{}",
+        file_name,
+        constants::JOURNAL_FILE_EXTENSION
+    )))
+}
+
+/// Read the journal of a save file, oldest entry first.
+///
+/// A missing journal is not an error; it simply means the save file has not
+/// been teleported yet.
+pub fn read_journal<P: AsRef<Path>>(
+    save_path: P,
+    config: &BackupConfig,
+) -> Result<Vec<JournalEntry>, TeleportError> {
+    let path = journal_path(save_path, config)?;
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(TeleportError::JournalError {
+                path: Some(path),
+                message: "cannot read the file".to_string(),
+                source: Some(Box::new(e)),
+            });
+        }
+    };
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            parse_entry(line).ok_or_else(|| TeleportError::JournalError {
+                path: Some(path.clone()),
+                message: format!("malformed entry on line {}", index + 1),
+                source: None,
+            })
+        })
+        .collect()
+}
+
+/// The most recent teleport that has not been undone yet, if any
+pub fn last_undoable(entries: &[JournalEntry]) -> Option<&JournalEntry> {
+    let mut outstanding = Vec::new();
+    for entry in entries {
+        match entry.action {
+            JournalAction::Teleport => outstanding.push(entry),
+            JournalAction::Undo => {
+                outstanding.pop();
+            }
+        }
+    }
+    outstanding.pop()
+}
+
+/// Undo the most recent teleport recorded in the journal of a save file.
+///
+/// Every byte the teleport overwrote (coordinates, facing and map ID alike)
+/// is restored exactly.
+/// The undo is refused with `TeleportError::JournalMismatch` if the file no
+/// longer matches the hash written by the last journaled edit, and with
+/// `TeleportError::NothingToUndo` if every teleport has been undone. The undo
+/// is recorded in the journal, so a second undo restores the teleport before.
+///
+/// # Returns
+///
+/// The journal entry of the teleport that was undone and the backup taken
+pub fn undo_last_teleport<P: AsRef<Path>>(
+    save_path: P,
+    options: &TeleportOptions,
+) -> Result<(JournalEntry, TeleportOutcome), TeleportError> {
+    let path = save_path.as_ref();
+    let entries = read_journal(path, &options.backups)?;
+    let undone = last_undoable(&entries)
+        .cloned()
+        .ok_or(TeleportError::NothingToUndo)?;
+
+    let last_hash = entries.last().map(|entry| entry.hash_after);
+    apply_edit(path, options, JournalAction::Undo, &undone.target, |save| {
+        if last_hash != Some(hash_bytes(save.bytes())) {
+            return Err(TeleportError::JournalMismatch(path.to_path_buf()));
+        }
+
+        // The teleport already wrote this block and left this map, so neither
+        // check has anything left to protect against
+        save.set_allow_ambiguous(true);
+        save.set_force_dlc(true);
+        save.restore(&undone.overwritten)?;
+        Ok(((), Vec::new()))
+    })
+    .map(|((), outcome)| (undone, outcome))
+}
+
+// ============================================================================
+// Crate-internal journal helpers
+// ============================================================================
+
+/// Load a save file, edit it and write it back with a backup, verification
+/// and journal entry.
+///
+/// `edit` makes the changes and returns its result together with any
+/// warnings about the destination. The journal entry is built before the
+/// file is written, so an edit that cannot be journaled is never written, and
+/// recorded as soon as the new contents have replaced the file.
+pub(crate) fn apply_edit<T>(
+    save_path: &Path,
+    options: &TeleportOptions,
+    action: JournalAction,
+    target: &str,
+    edit: impl FnOnce(&mut SaveFile) -> Result<(T, Vec<TeleportWarning>), TeleportError>,
+) -> Result<(T, TeleportOutcome), TeleportError> {
+    let mut save = SaveFile::load(save_path)?;
+    save.set_allow_ambiguous(options.allow_ambiguous);
+    save.set_force_dlc(options.force_dlc);
+    save.set_write_facing(options.write_facing);
+    let snapshot = Snapshot::take(&save)?;
+    let (value, mut warnings) = edit(&mut save)?;
+    let entry = snapshot.entry(action, target, &save)?;
+    let (backup, warning) = save.save_journaled(&options.backups, entry)?;
+    warnings.extend(warning);
+
+    Ok((value, TeleportOutcome { backup, warnings }))
+}
+
+/// Contents, position and hash of a save file before it is edited
+pub(crate) struct Snapshot {
+    bytes: Vec<u8>,
+    position: CurrentPosition,
+    hash: u64,
+}
+
+/// A journal entry built before the save file is written, recorded once the
+/// write has succeeded
+pub(crate) struct PendingEntry {
+    save_path: PathBuf,
+    entry: JournalEntry,
+}
+
+impl Snapshot {
+    /// Record the state of a save file that is about to be edited
+    pub(crate) fn take(save: &SaveFile) -> Result<Self, TeleportError> {
+        Ok(Self {
+            bytes: save.bytes().to_vec(),
+            position: save.position()?,
+            hash: hash_bytes(save.bytes()),
+        })
+    }
+
+    /// Build the journal entry for the edits made to `save` since the
+    /// snapshot was taken.
+    ///
+    /// Called before the file is written, so an edit that cannot be
+    /// journaled fails with `TeleportError::JournalError` (or the error of
+    /// reading its position) instead of being written without one.
+    pub(crate) fn entry(
+        self,
+        action: JournalAction,
+        target: &str,
+        save: &SaveFile,
+    ) -> Result<PendingEntry, TeleportError> {
+        let save_path = save
+            .path()
+            .ok_or_else(|| TeleportError::JournalError {
+                path: None,
+                message: "the save was not loaded from a file".to_string(),
+                source: None,
+            })?
+            .to_path_buf();
+
+        Ok(PendingEntry {
+            save_path,
+            entry: JournalEntry {
+                time: SystemTime::now(),
+                action,
+                target: target.to_string(),
+                from: self.position,
+                to: save.position()?,
+                overwritten: changed_ranges(&self.bytes, save.bytes()),
+                hash_before: self.hash,
+                hash_after: hash_bytes(save.bytes()),
+            },
+        })
+    }
+}
+
+impl PendingEntry {
+    /// Append the entry to the journal of the written save file.
+    ///
+    /// The new contents have already replaced the file, so a journal that
+    /// cannot be written is reported as a warning.
+    pub(crate) fn record(self, config: &BackupConfig) -> Option<TeleportWarning> {
+        append(&self.save_path, config, &self.entry)
+            .err()
+            .map(|e| TeleportWarning::NotJournaled(e.to_string()))
+    }
+}
+
+// ============================================================================
+// Private helper functions
+// ============================================================================
+
+/// Append an entry to the journal of a save file, creating it if needed
+fn append(
+    save_path: &Path,
+    config: &BackupConfig,
+    entry: &JournalEntry,
+) -> Result<(), TeleportError> {
+    let path = journal_path(save_path, config)?;
+    let error = |e: std::io::Error| TeleportError::JournalError {
+        path: Some(path.clone()),
+        message: "cannot append to the file".to_string(),
+        source: Some(Box::new(e)),
+    };
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(error)?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(error)?;
+    writeln!(file, "{}", format_entry(entry)).map_err(error)?;
+    file.sync_all().map_err(error)
+}
+
+/// Offset and `before` contents of every run of bytes that differs between
+/// `before` and `after`
+fn changed_ranges(before: &[u8], after: &[u8]) -> Vec<(usize, Vec<u8>)> {
+    let mut ranges: Vec<(usize, Vec<u8>)> = Vec::new();
+    for (offset, (&old, &new)) in before.iter().zip(after).enumerate() {
+        if old == new {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some((start, bytes)) if *start + bytes.len() == offset => bytes.push(old),
+            _ => ranges.push((offset, vec![old])),
+        }
+    }
+    ranges
+}
+
+/// 64-bit FNV-1a hash of the file contents
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(constants::FNV_OFFSET_BASIS, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(constants::FNV_PRIME)
+        })
+}
+
+/// Tab-separated line: unix millis, action, both hashes, both positions, the
+/// overwritten bytes and the target. Coordinates are stored as raw f32 bits.
+fn format_entry(entry: &JournalEntry) -> String {
+    let millis = entry
+        .time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    let action = match entry.action {
+        JournalAction::Teleport => "teleport",
+        JournalAction::Undo => "undo",
+    };
+    // Tabs and line breaks would split the entry
+    let target: String = entry
+        .target
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+
+    format!(
+        "{}\t{}\t{:016x}\t{:016x}\t{}\t{}\t{}\t{}",
+        millis,
+        action,
+        entry.hash_before,
+        entry.hash_after,
+        format_position(&entry.from),
+        format_position(&entry.to),
+        format_ranges(&entry.overwritten),
+        target
+    )
+}
+
+fn parse_entry(line: &str) -> Option<JournalEntry> {
+    let mut fields = line.splitn(8, '\t');
+    let millis: u64 = fields.next()?.parse().ok()?;
+    let action = match fields.next()? {
+        "teleport" => JournalAction::Teleport,
+        "undo" => JournalAction::Undo,
+        _ => return None,
+    };
+    let hash_before = u64::from_str_radix(fields.next()?, 16).ok()?;
+    let hash_after = u64::from_str_radix(fields.next()?, 16).ok()?;
+    let from = parse_position(fields.next()?)?;
+    let to = parse_position(fields.next()?)?;
+    let overwritten = parse_ranges(fields.next()?)?;
+    let target = fields.next()?.to_string();
+
+    Some(JournalEntry {
+        time: UNIX_EPOCH + Duration::from_millis(millis),
+        action,
+        target,
+        from,
+        to,
+        overwritten,
+        hash_before,
+        hash_after,
+    })
+}
+
+/// `<x bits>,<y bits>,<z bits>,<map ID>`, all in hex
+fn format_position(position: &CurrentPosition) -> String {
+    format!(
+        "{:08x},{:08x},{:08x},{:08x}",
+        position.x.to_bits(),
+        position.y.to_bits(),
+        position.z.to_bits(),
+        u32::from_be_bytes(position.map_id)
+    )
+}
+
+fn parse_position(text: &str) -> Option<CurrentPosition> {
+    let parts = text
+        .split(',')
+        .map(|part| u32::from_str_radix(part, 16).ok())
+        .collect::<Option<Vec<u32>>>()?;
+    let [x, y, z, map_id] = parts[..] else {
+        return None;
+    };
+    Some(CurrentPosition {
+        x: f32::from_bits(x),
+        y: f32::from_bits(y),
+        z: f32::from_bits(z),
+        map_id: map_id.to_be_bytes(),
+    })
+}
+
+/// `<offset>:<bytes>` for each range, comma-separated and in hex, or `-` if
+/// nothing changed
+fn format_ranges(ranges: &[(usize, Vec<u8>)]) -> String {
+    if ranges.is_empty() {
+        return "-".to_string();
+    }
+    ranges
+        .iter()
+        .map(|(offset, bytes)| {
+            let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+            format!("{:x}:{}", offset, hex)
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn parse_ranges(text: &str) -> Option<Vec<(usize, Vec<u8>)>> {
+    if text == "-" {
+        return Some(Vec::new());
+    }
+    text.split(',')
+        .map(|range| {
+            let (offset, hex) = range.split_once(':')?;
+            if hex.is_empty() || hex.len() % 2 != 0 {
+                return None;
+            }
+            let bytes = (0..hex.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+                .collect::<Option<Vec<u8>>>()?;
+            Some((usize::from_str_radix(offset, 16).ok()?, bytes))
+        })
+        .collect()
+}
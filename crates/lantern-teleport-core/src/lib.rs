@@ -3,17 +3,55 @@
 //! This library provides functions to work with Bloodborne lantern teleport locations.
 
 use std::fs;
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::Path;
 
 // ============================================================================
 // Module declarations
 // ============================================================================
 
+mod atomic;
+mod backup;
+#[cfg(feature = "user-files")]
+mod bookmarks;
+mod catalog;
 mod constants;
+mod journal;
+mod map_id;
+mod save_file;
+mod slots;
 mod types;
 
 // Re-export public types
-pub use types::{CurrentPosition, Location, TeleportError};
+pub use types::{
+    BackupConfig, BackupInfo, CharacterInfo, CoordinateCandidate, CurrentPosition, LanternStatus,
+    Location, NearbyLocation, OwnedLocation, PositionInfo, Region, TeleportError, TeleportOptions,
+    TeleportOutcome, TeleportWarning, WorldState,
+};
+
+// Re-export typed map IDs
+pub use map_id::{MapArea, MapId};
+
+// Re-export the parsed save file type
+pub use save_file::SaveFile;
+
+// Re-export the location catalog
+pub use catalog::Catalog;
+
+// Re-export bookmarks
+#[cfg(feature = "user-files")]
+pub use bookmarks::{Bookmark, BookmarkStore, teleport_to_bookmark};
+
+// Re-export character slot scanning
+pub use slots::{SaveSlot, scan_slots};
+
+// Re-export the teleport journal
+pub use journal::{
+    JournalAction, JournalEntry, journal_path, last_undoable, read_journal, undo_last_teleport,
+};
+
+// Re-export backup store functions
+pub use backup::{create_backup, inspect_backup, list_backups, restore_backup};
 
 // Re-export public constants
 pub use constants::LOCATIONS;
@@ -24,10 +62,37 @@ pub use constants::LOCATIONS;
 
 /// Get a reference to all available lantern locations.
 ///
-/// Returns a fixed-size slice of all teleport locations.
+/// Returns the catalog installed with [`Catalog::install`], or the built-in
+/// lanterns if no catalog was installed.
 #[inline]
 pub fn get_all_locations() -> &'static [Location] {
-    &constants::LOCATIONS
+    catalog::installed_locations()
+}
+
+/// Get all available locations grouped by region.
+///
+/// Regions are returned in the order they first appear in the catalog.
+#[inline]
+pub fn locations_by_region() -> Vec<(&'static str, Vec<&'static Location>)> {
+    catalog::group_by_region(get_all_locations())
+}
+
+/// Get all available locations as owned regions.
+///
+/// The owned counterpart of [`locations_by_region`], suitable for
+/// serialization (with the `serde` feature).
+#[inline]
+pub fn regions() -> Vec<Region> {
+    catalog::to_regions(get_all_locations())
+}
+
+/// Get all map IDs that can be used as a teleport destination.
+///
+/// These are the map IDs accepted by [`Location::custom`]. See [`MapId::all`]
+/// for the typed equivalent.
+#[inline]
+pub fn known_map_ids() -> &'static [[u8; 2]] {
+    &constants::map_ids::KNOWN
 }
 
 /// Search for locations matching the given query string.
@@ -43,16 +108,93 @@ pub fn get_all_locations() -> &'static [Location] {
 /// A vector of references to all locations that match the query
 pub fn search_locations(query: &str) -> Vec<&'static Location> {
     let lower_query = query.to_lowercase();
-    constants::LOCATIONS
+    get_all_locations()
         .iter()
         .filter(|loc| loc.name.to_lowercase().contains(&lower_query))
         .collect()
 }
 
+/// Identify the map area of a position and the nearest catalog location.
+///
+/// Only locations on the same map as the position are considered, since
+/// coordinates on different maps are not comparable.
+///
+/// # Arguments
+///
+/// * `position` - A position extracted from a save file
+///
+/// # Returns
+///
+/// The area and nearest location, or `None` if the position's map ID is unknown
+pub fn identify_position(position: &CurrentPosition) -> Option<PositionInfo> {
+    let map = position.map().ok()?;
+    let map_id = map.to_bytes();
+
+    let nearest = get_all_locations()
+        .iter()
+        .filter(|loc| loc.map_id == map_id)
+        .map(|loc| NearbyLocation {
+            location: loc,
+            distance: distance(position, loc),
+        })
+        .min_by(|a, b| a.distance.total_cmp(&b.distance));
+
+    Some(PositionInfo { map, nearest })
+}
+
+/// Parse a coordinate triple in the form "x,y,z", as used for custom
+/// destinations.
+///
+/// Whitespace around each number is ignored.
+///
+/// # Returns
+///
+/// The coordinates, or `TeleportError::InvalidCoordinates` if the text is not
+/// three numbers and `TeleportError::NonFiniteCoordinate` if one is NaN or
+/// infinite
+pub fn parse_coordinates(text: &str) -> Result<[f32; 3], TeleportError> {
+    let invalid = || TeleportError::InvalidCoordinates(text.to_string());
+    let parts: Vec<&str> = text.split(',').map(str::trim).collect();
+    let [x, y, z] = parts.as_slice() else {
+        return Err(invalid());
+    };
+
+    let parse = |s: &str| s.parse::<f32>().map_err(|_| invalid());
+    let [x, y, z] = [parse(x)?, parse(y)?, parse(z)?];
+    types::ensure_finite(x, y, z)?;
+    Ok([x, y, z])
+}
+
+/// Look up the event flag that records whether a location's lantern is lit.
+///
+/// Only the lanterns of the built-in catalog have a known flag. The flag
+/// numbers are unverified, so this needs the `experimental` feature.
+#[cfg(feature = "experimental")]
+#[inline]
+pub fn lantern_flag(location: &Location) -> Option<u32> {
+    constants::lantern_flags::flag_of(location.name)
+}
+
+/// Report which lanterns of the installed catalog are lit in a save file.
+///
+/// # Arguments
+///
+/// * `save_path` - Path to the Bloodborne save file
+///
+/// # Returns
+///
+/// One status per catalog location, in catalog order, or a `TeleportError`
+/// if the file is invalid or its event flags lie outside the file
+#[cfg(feature = "experimental")]
+pub fn lantern_report<P: AsRef<Path>>(save_path: P) -> Result<Vec<LanternStatus>, TeleportError> {
+    SaveFile::load(save_path)?.lantern_statuses()
+}
+
 /// Teleport to the specified location in a Bloodborne save file.
 ///
 /// This function modifies the save file at the given path to teleport
-/// the character to the specified location.
+/// the character to the specified location. A backup of the save file is
+/// taken first using the default `BackupConfig`.
 ///
 /// # Arguments
 ///
@@ -62,26 +204,142 @@ pub fn search_locations(query: &str) -> Vec<&'static Location> {
 /// # Returns
 ///
 /// `Ok(())` on success, or a `TeleportError` if the operation fails
+#[inline]
 pub fn teleport<P: AsRef<Path>>(save_path: P, location: &Location) -> Result<(), TeleportError> {
-    // Read the save file
-    let path = save_path.as_ref();
-    let mut bytes = fs::read(path).map_err(|e| TeleportError::ReadError(e.to_string()))?;
-
-    // Find LCED marker
-    let lced_offset = find_lced_marker(&bytes).ok_or(TeleportError::LcedMarkerNotFound)?;
+    teleport_with_options(save_path, location, &TeleportOptions::default()).map(|_| ())
+}
 
-    // Find coordinates offset
-    let coord_offset =
-        find_coordinates_offset(&bytes, lced_offset).ok_or(TeleportError::CoordPatternNotFound)?;
+/// Teleport to the specified location with explicit options.
+///
+/// Behaves like [`teleport`], but allows configuring the backup store.
+/// The save file is only backed up once it has been validated, so an
+/// invalid file never produces a backup.
+///
+/// The new contents are written atomically (temp file, fsync, rename) and
+/// then read back; if the position in the written file does not match the
+/// requested location, `TeleportError::VerificationFailed` is returned.
+///
+/// Once written, the teleport is recorded in the save file's journal so it
+/// can be undone with [`undo_last_teleport`].
+///
+/// Destinations the save may not be ready for (a DLC map without DLC
+/// progress, or with the `experimental` feature an unlit lantern) are
+/// written anyway and reported in [`TeleportOutcome::warnings`].
+///
+/// To make several edits with a single parse, use [`SaveFile`] directly.
+///
+/// # Arguments
+///
+/// * `save_path` - Path to the Bloodborne save file
+/// * `location` - The destination location
+/// * `options` - Options controlling backups and safety checks
+///
+/// # Returns
+///
+/// `Ok(TeleportOutcome)` describing the backup taken, or a `TeleportError`
+/// if the operation fails
+pub fn teleport_with_options<P: AsRef<Path>>(
+    save_path: P,
+    location: &Location,
+    options: &TeleportOptions,
+) -> Result<TeleportOutcome, TeleportError> {
+    journal::apply_edit(
+        save_path.as_ref(),
+        options,
+        JournalAction::Teleport,
+        location.name,
+        |save| {
+            save.teleport_to(location)?;
+            #[cfg_attr(not(feature = "experimental"), allow(unused_mut))]
+            let mut warnings = save.destination_warnings(&location.map_id);
+            #[cfg(feature = "experimental")]
+            warnings.extend(save.lantern_warning(location));
+            Ok(((), warnings))
+        },
+    )
+    .map(|((), outcome)| outcome)
+}
 
-    // Write the new coordinates and map ID
-    write_map_id(&mut bytes, location);
-    write_coordinates(&mut bytes, coord_offset, location);
+/// Move the character of one save file to where the character of another
+/// save file stands.
+///
+/// The coordinates and map ID of `source_path` are written to `save_path`,
+/// and its facing too if `options.write_facing` is set and the facing can be
+/// read (the facing offset is experimental). `save_path` gets the same checks, backup,
+/// atomic write, verification and journal entry as
+/// [`teleport_with_options`]. The source file is only read.
+///
+/// # Arguments
+///
+/// * `source_path` - Save file whose position is copied
+/// * `save_path` - Save file whose character is moved
+/// * `options` - Options controlling backups and safety checks
+///
+/// # Returns
+///
+/// The copied position and the outcome of the write, or a `TeleportError`
+/// if either file is invalid or the write fails
+pub fn copy_position<P: AsRef<Path>, Q: AsRef<Path>>(
+    source_path: P,
+    save_path: Q,
+    options: &TeleportOptions,
+) -> Result<(CurrentPosition, TeleportOutcome), TeleportError> {
+    let source_path = source_path.as_ref();
+    let source = SaveFile::load(source_path)?;
+    let position = source.position()?;
 
-    // Write the modified save file back
-    fs::write(path, &bytes).map_err(|e| TeleportError::WriteError(e.to_string()))?;
+    journal::apply_edit(
+        save_path.as_ref(),
+        options,
+        JournalAction::Teleport,
+        &format!("the position of {}", source_path.display()),
+        |save| {
+            save.set_map_id(position.map_id)?;
+            save.set_position(position.x, position.y, position.z)?;
+            if let Some(facing) = source.facing().filter(|_| options.write_facing) {
+                save.set_facing(facing)?;
+            }
+            let map_id = constants::map_ids::from_save_format(&position.map_id);
+            Ok((position, save.destination_warnings(&map_id)))
+        },
+    )
+}
 
-    Ok(())
+/// Move the character of a save file by a small offset, to free it from
+/// geometry it is stuck in.
+///
+/// The offsets are added to the position read by [`validate_save_file`]; the
+/// map ID stays the same. The save file gets the same backup, atomic write,
+/// verification and journal entry as [`teleport_with_options`], so a nudge
+/// can be undone with [`undo_last_teleport`].
+///
+/// # Arguments
+///
+/// * `save_path` - Path to the Bloodborne save file
+/// * `dx`, `dy`, `dz` - Offsets along each axis
+/// * `options` - Options controlling backups and safety checks
+///
+/// # Returns
+///
+/// The new position and the outcome of the write, or a `TeleportError`
+/// (`NonFiniteCoordinate` if an offset or the new position is not finite)
+pub fn nudge<P: AsRef<Path>>(
+    save_path: P,
+    dx: f32,
+    dy: f32,
+    dz: f32,
+    options: &TeleportOptions,
+) -> Result<(CurrentPosition, TeleportOutcome), TeleportError> {
+    journal::apply_edit(
+        save_path.as_ref(),
+        options,
+        JournalAction::Teleport,
+        &format!(
+            "the position nudged by ({:+.2}, {:+.2}, {:+.2})",
+            dx, dy, dz
+        ),
+        |save| Ok((save.nudge(dx, dy, dz)?, Vec::new())),
+    )
 }
 
 /// Validate a save file and extract the current position.
@@ -98,81 +356,132 @@ pub fn teleport<P: AsRef<Path>>(save_path: P, location: &Location) -> Result<(),
 /// `Ok(CurrentPosition)` with the current coordinates and map ID if valid,
 /// or a `TeleportError` if the file is invalid or cannot be read.
 pub fn validate_save_file<P: AsRef<Path>>(save_path: P) -> Result<CurrentPosition, TeleportError> {
-    // Read the save file
-    let path = save_path.as_ref();
-    let bytes = fs::read(path).map_err(|e| TeleportError::ReadError(e.to_string()))?;
-
-    // Find LCED marker
-    let lced_offset = find_lced_marker(&bytes).ok_or(TeleportError::LcedMarkerNotFound)?;
-
-    // Find coordinates offset
-    let coord_offset =
-        find_coordinates_offset(&bytes, lced_offset).ok_or(TeleportError::CoordPatternNotFound)?;
-
-    // Extract current position
-    let x = f32::from_le_bytes(
-        bytes[coord_offset..coord_offset + 4]
-            .try_into()
-            .map_err(|_| TeleportError::InvalidOffset)?,
-    );
-    let y = f32::from_le_bytes(
-        bytes[coord_offset + 4..coord_offset + 8]
-            .try_into()
-            .map_err(|_| TeleportError::InvalidOffset)?,
-    );
-    let z = f32::from_le_bytes(
-        bytes[coord_offset + 8..coord_offset + 12]
-            .try_into()
-            .map_err(|_| TeleportError::InvalidOffset)?,
-    );
-    let map_id: [u8; 4] = bytes[0x04..0x08]
-        .try_into()
-        .map_err(|_| TeleportError::InvalidOffset)?;
-
-    Ok(CurrentPosition { x, y, z, map_id })
+    let save_path = save_path.as_ref();
+    let bytes = fs::read(save_path).map_err(|source| TeleportError::ReadError {
+        path: Some(save_path.to_path_buf()),
+        source,
+    })?;
+    validate_save_bytes(&bytes)
 }
 
 // ============================================================================
-// Private helper functions
+// In-memory API
 // ============================================================================
 
+/// Validate save file bytes and extract the current position.
+///
+/// The in-memory counterpart of [`validate_save_file`].
 #[inline]
-fn find_lced_marker(bytes: &[u8]) -> Option<usize> {
-    for i in 0..(bytes.len().saturating_sub(4)) {
-        if bytes[i..i + 4] == constants::LCED_MARKER_BYTES {
-            return Some(i);
-        }
-    }
-    None
+pub fn validate_save_bytes(bytes: &[u8]) -> Result<CurrentPosition, TeleportError> {
+    let offsets = save_file::locate(bytes)?;
+    save_file::read_position(bytes, offsets.coords)
 }
 
-#[inline]
-fn find_coordinates_offset(bytes: &[u8], lced_offset: usize) -> Option<usize> {
-    let search_start = lced_offset;
-    let search_end = bytes
-        .len()
-        .saturating_sub(constants::COORD_PATTERN_BYTES.len());
+/// Read the character's name, level and other metadata from save file bytes.
+///
+/// The character offsets are unverified, so treat the values as a hint.
+/// Fails only if the save itself is invalid; fields that cannot be read are
+/// `None` (see [`CharacterInfo`]).
+pub fn read_character_bytes(bytes: &[u8]) -> Result<CharacterInfo, TeleportError> {
+    let offsets = save_file::locate(bytes)?;
+    Ok(save_file::read_character(bytes, offsets.lced))
+}
 
-    for i in search_start..search_end {
-        if bytes[i..i + constants::COORD_PATTERN_BYTES.len()] == constants::COORD_PATTERN_BYTES {
-            return Some(i + constants::COORD_OFFSET_AFTER_PATTERN_BYTES);
-        }
-    }
-    None
+/// Read the story progress (evening, night or Blood Moon) from save file
+/// bytes.
+///
+/// Fails if the save is invalid or its event flags lie outside the file.
+#[cfg(feature = "experimental")]
+pub fn world_state_bytes(bytes: &[u8]) -> Result<WorldState, TeleportError> {
+    let offsets = save_file::locate(bytes)?;
+    save_file::world_state(bytes, offsets.lced)
 }
 
-#[inline]
-fn write_coordinates(bytes: &mut [u8], offset: usize, location: &Location) {
-    bytes[offset..offset + 4].copy_from_slice(&f32::to_le_bytes(location.x));
-    bytes[offset + 4..offset + 8].copy_from_slice(&f32::to_le_bytes(location.y));
-    bytes[offset + 8..offset + 12].copy_from_slice(&f32::to_le_bytes(location.z));
+/// Report which lanterns of the installed catalog are lit.
+///
+/// The in-memory counterpart of [`lantern_report`].
+#[cfg(feature = "experimental")]
+pub fn lantern_report_bytes(bytes: &[u8]) -> Result<Vec<LanternStatus>, TeleportError> {
+    let offsets = save_file::locate(bytes)?;
+    save_file::lantern_statuses(bytes, offsets.lced, catalog::installed_locations())
+}
+
+/// List every block after the LCED marker that could hold the coordinates.
+///
+/// Each candidate carries the floats decoded at its offset and a plausibility
+/// score based on finiteness and the coordinate ranges of the known
+/// locations. The first candidate is the one the other functions read and
+/// write.
+pub fn scan_coordinate_candidates(bytes: &[u8]) -> Result<Vec<CoordinateCandidate>, TeleportError> {
+    let (_, candidates) = save_file::locate_with_candidates(bytes)?;
+    Ok(save_file::score_candidates(bytes, &candidates))
 }
 
+/// Teleport to the specified location by patching save file bytes in place.
+///
+/// The in-memory counterpart of [`teleport`]. No backup is taken and nothing
+/// is written to disk; the caller owns the bytes and decides what to do with them.
+///
+/// Fails with `TeleportError::MultipleCoordinateBlocks` if the save has more
+/// than one plausible coordinate block; use [`SaveFile::set_allow_ambiguous`]
+/// to write anyway. Nothing is returned to carry warnings, so use
+/// [`SaveFile::destination_warnings`] to check the destination first. The
+/// location's facing is not written; use [`SaveFile::set_write_facing`] for
+/// that.
+///
+/// # Arguments
+///
+/// * `bytes` - Contents of a Bloodborne save file
+/// * `location` - The destination location
+pub fn teleport_bytes(bytes: &mut [u8], location: &Location) -> Result<(), TeleportError> {
+    let (offsets, candidates) = save_file::locate_with_candidates(bytes)?;
+    save_file::ensure_unambiguous(bytes, &candidates)?;
+    save_file::apply_location(bytes, offsets.coords, location, false)
+}
+
+/// Read a save file from any reader and extract the current position.
+pub fn read_position<R: Read>(mut reader: R) -> Result<CurrentPosition, TeleportError> {
+    let mut bytes = Vec::new();
+    reader
+        .read_to_end(&mut bytes)
+        .map_err(|source| TeleportError::ReadError { path: None, source })?;
+    validate_save_bytes(&bytes)
+}
+
+/// Teleport to the specified location in a seekable stream holding a save file.
+///
+/// The whole stream is read from the start, patched, and written back from
+/// the start. The save file's length never changes, so nothing is truncated.
+pub fn teleport_stream<S: Read + Write + Seek>(
+    mut stream: S,
+    location: &Location,
+) -> Result<(), TeleportError> {
+    let mut bytes = Vec::new();
+    stream
+        .seek(SeekFrom::Start(0))
+        .and_then(|_| stream.read_to_end(&mut bytes))
+        .map_err(|source| TeleportError::ReadError { path: None, source })?;
+
+    teleport_bytes(&mut bytes, location)?;
+
+    stream
+        .seek(SeekFrom::Start(0))
+        .and_then(|_| stream.write_all(&bytes))
+        .and_then(|_| stream.flush())
+        .map_err(|source| TeleportError::WriteError { path: None, source })?;
+
+    Ok(())
+}
+
+// ============================================================================
+// Private helper functions
+// ============================================================================
+
+/// Straight-line distance between a position and a location
 #[inline]
-fn write_map_id(bytes: &mut [u8], location: &Location) {
-    let save_format = constants::map_ids::to_save_format(&location.map_id);
-    bytes[0x04] = save_format[0];
-    bytes[0x05] = save_format[1];
-    bytes[0x06] = save_format[2];
-    bytes[0x07] = save_format[3];
+fn distance(position: &CurrentPosition, location: &Location) -> f32 {
+    let dx = position.x - location.x;
+    let dy = position.y - location.y;
+    let dz = position.z - location.z;
+    (dx * dx + dy * dy + dz * dz).sqrt()
 }
@@ -0,0 +1,242 @@
+//! Typed map IDs and map areas
+//!
+//! A map ID is two bytes: a variant index followed by an area number. Save
+//! files store it padded to four bytes (`00 00 <variant> <area>`). Several
+//! variants of the same area exist, e.g. Central Yharnam and the two parts of
+//! Cathedral Ward are all variants of the Yharnam area.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::constants::map_ids;
+use crate::types::{TeleportError, WorldState};
+
+/// A map area, shared by all variants of a map
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MapArea {
+    /// Hunter's Dream (including the Abandoned Old Workshop)
+    HuntersDream,
+    /// Hemwick Charnel Lane
+    Hemwick,
+    /// Old Yharnam
+    OldYharnam,
+    /// Central Yharnam and Cathedral Ward
+    Yharnam,
+    /// Forsaken Castle Cainhurst
+    Cainhurst,
+    /// Nightmare of Mensis
+    NightmareOfMensis,
+    /// Forbidden Woods
+    ForbiddenWoods,
+    /// Yahar'gul, Unseen Village
+    Yahargul,
+    /// Byrgenwerth and the Lecture Building
+    Byrgenwerth,
+    /// Nightmare Frontier
+    NightmareFrontier,
+    /// Hunter's Nightmare (The Old Hunters)
+    HuntersNightmare,
+    /// Research Hall (The Old Hunters)
+    ResearchHall,
+    /// Fishing Hamlet (The Old Hunters)
+    FishingHamlet,
+}
+
+impl MapArea {
+    /// Look up an area by its area number (the second map ID byte)
+    pub fn from_number(number: u8) -> Option<Self> {
+        Some(match number {
+            0x15 => Self::HuntersDream,
+            0x16 => Self::Hemwick,
+            0x17 => Self::OldYharnam,
+            0x18 => Self::Yharnam,
+            0x19 => Self::Cainhurst,
+            0x1A => Self::NightmareOfMensis,
+            0x1B => Self::ForbiddenWoods,
+            0x1C => Self::Yahargul,
+            0x20 => Self::Byrgenwerth,
+            0x21 => Self::NightmareFrontier,
+            0x22 => Self::HuntersNightmare,
+            0x23 => Self::ResearchHall,
+            0x24 => Self::FishingHamlet,
+            _ => return None,
+        })
+    }
+
+    /// Area number (the second map ID byte)
+    pub fn number(self) -> u8 {
+        match self {
+            Self::HuntersDream => 0x15,
+            Self::Hemwick => 0x16,
+            Self::OldYharnam => 0x17,
+            Self::Yharnam => 0x18,
+            Self::Cainhurst => 0x19,
+            Self::NightmareOfMensis => 0x1A,
+            Self::ForbiddenWoods => 0x1B,
+            Self::Yahargul => 0x1C,
+            Self::Byrgenwerth => 0x20,
+            Self::NightmareFrontier => 0x21,
+            Self::HuntersNightmare => 0x22,
+            Self::ResearchHall => 0x23,
+            Self::FishingHamlet => 0x24,
+        }
+    }
+
+    /// Human-readable area name
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::HuntersDream => "Hunter's Dream",
+            Self::Hemwick => "Hemwick Charnel Lane",
+            Self::OldYharnam => "Old Yharnam",
+            Self::Yharnam => "Yharnam",
+            Self::Cainhurst => "Forsaken Castle Cainhurst",
+            Self::NightmareOfMensis => "Nightmare of Mensis",
+            Self::ForbiddenWoods => "Forbidden Woods",
+            Self::Yahargul => "Yahar'gul, Unseen Village",
+            Self::Byrgenwerth => "Byrgenwerth",
+            Self::NightmareFrontier => "Nightmare Frontier",
+            Self::HuntersNightmare => "Hunter's Nightmare",
+            Self::ResearchHall => "Research Hall",
+            Self::FishingHamlet => "Fishing Hamlet",
+        }
+    }
+
+    /// Whether the area only exists with The Old Hunters DLC
+    pub fn is_dlc(self) -> bool {
+        matches!(
+            self,
+            Self::HuntersNightmare | Self::ResearchHall | Self::FishingHamlet
+        )
+    }
+}
+
+impl fmt::Display for MapArea {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A known map ID: an area together with a variant index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MapId {
+    area: MapArea,
+    variant: u8,
+}
+
+impl MapId {
+    /// Parse a 2-byte map ID (`[variant, area]`), rejecting unknown IDs
+    pub fn from_bytes(bytes: [u8; 2]) -> Result<Self, TeleportError> {
+        if !map_ids::is_known(&bytes) {
+            return Err(TeleportError::UnknownMapId(bytes));
+        }
+
+        let area = MapArea::from_number(bytes[1]).ok_or(TeleportError::UnknownMapId(bytes))?;
+        Ok(Self {
+            area,
+            variant: bytes[0],
+        })
+    }
+
+    /// Parse the 4-byte map ID stored in save files, rejecting unknown IDs
+    pub fn from_save_format(bytes: [u8; 4]) -> Result<Self, TeleportError> {
+        if bytes[0] != 0 || bytes[1] != 0 {
+            return Err(TeleportError::InvalidMapId(format!(
+                "{:02X}{:02X}{:02X}{:02X}",
+                bytes[0], bytes[1], bytes[2], bytes[3]
+            )));
+        }
+        Self::from_bytes(map_ids::from_save_format(&bytes))
+    }
+
+    /// All known map IDs
+    pub fn all() -> impl Iterator<Item = MapId> {
+        map_ids::KNOWN
+            .iter()
+            .filter_map(|&bytes| Self::from_bytes(bytes).ok())
+    }
+
+    /// 2-byte form (`[variant, area]`)
+    #[inline]
+    pub fn to_bytes(self) -> [u8; 2] {
+        [self.variant, self.area.number()]
+    }
+
+    /// 4-byte form stored in save files
+    #[inline]
+    pub fn to_save_format(self) -> [u8; 4] {
+        map_ids::to_save_format(&self.to_bytes())
+    }
+
+    /// The area this map belongs to
+    #[inline]
+    pub fn area(self) -> MapArea {
+        self.area
+    }
+
+    /// Variant index of the area
+    #[inline]
+    pub fn variant(self) -> u8 {
+        self.variant
+    }
+
+    /// Whether the map only exists with The Old Hunters DLC
+    #[inline]
+    pub fn is_dlc(self) -> bool {
+        self.area.is_dlc()
+    }
+
+    /// Earliest story progress in which the game loads this map variant
+    #[inline]
+    pub fn required_world_state(self) -> WorldState {
+        map_ids::required_world_state(&self.to_bytes())
+    }
+
+    /// Human-readable name of this specific map variant
+    pub fn name(self) -> &'static str {
+        map_ids::variant_name(&self.to_bytes()).unwrap_or(self.area.name())
+    }
+
+    /// The ID in `AA:BB` hex notation
+    pub fn to_hex(self) -> String {
+        let [variant, area] = self.to_bytes();
+        format!("{:02X}:{:02X}", variant, area)
+    }
+}
+
+impl fmt::Display for MapId {
+    /// Formats as the variant name, e.g. "Cathedral Ward"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for MapId {
+    type Err = TeleportError;
+
+    /// Parse `AA:BB` hex notation (e.g. `01:18`), rejecting unknown IDs
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || TeleportError::InvalidMapId(s.to_string());
+
+        let (variant, area) = s.trim().split_once(':').ok_or_else(invalid)?;
+        let variant = u8::from_str_radix(variant.trim(), 16).map_err(|_| invalid())?;
+        let area = u8::from_str_radix(area.trim(), 16).map_err(|_| invalid())?;
+
+        Self::from_bytes([variant, area])
+    }
+}
+
+impl TryFrom<[u8; 2]> for MapId {
+    type Error = TeleportError;
+
+    #[inline]
+    fn try_from(bytes: [u8; 2]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl From<MapId> for [u8; 2] {
+    #[inline]
+    fn from(map_id: MapId) -> Self {
+        map_id.to_bytes()
+    }
+}
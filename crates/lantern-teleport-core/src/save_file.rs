@@ -0,0 +1,910 @@
+//! Parsed save file with a load / inspect / mutate / save lifecycle
+//!
+//! A `SaveFile` keeps the raw bytes of a save together with the offsets found
+//! while parsing it, so several queries and edits only need a single read and
+//! a single scan. Save files can be loaded from disk or parsed from bytes
+//! already held in memory.
+
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use memchr::memmem::Finder;
+
+use crate::atomic;
+use crate::backup;
+use crate::catalog;
+use crate::constants;
+use crate::journal;
+use crate::map_id::MapId;
+use crate::types::{
+    self, BackupConfig, BackupInfo, CharacterInfo, CoordinateCandidate, CurrentPosition, Location,
+    TeleportError, TeleportWarning,
+};
+#[cfg(feature = "experimental")]
+use crate::types::{LanternStatus, WorldState};
+
+/// A parsed Bloodborne save file
+#[derive(Debug, Clone)]
+pub struct SaveFile {
+    path: Option<PathBuf>,
+    bytes: Vec<u8>,
+    lced_offset: usize,
+    coord_offset: usize,
+    candidates: Vec<usize>,
+    dirty: bool,
+    allow_ambiguous: bool,
+    force_dlc: bool,
+    write_facing: bool,
+}
+
+impl SaveFile {
+    /// Read and parse the save file at the given path.
+    ///
+    /// Fails if the file cannot be read or does not contain the LCED marker
+    /// and coordinate pattern.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, TeleportError> {
+        let path = path.as_ref();
+        let bytes = fs::read(path).map_err(|source| TeleportError::ReadError {
+            path: Some(path.to_path_buf()),
+            source,
+        })?;
+
+        let mut save = Self::from_bytes(bytes)?;
+        save.path = Some(path.to_path_buf());
+        Ok(save)
+    }
+
+    /// Parse a save file held in memory.
+    ///
+    /// The resulting `SaveFile` has no path, so it can only be written with
+    /// [`SaveFile::save_as`] or taken back out with [`SaveFile::into_bytes`].
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, TeleportError> {
+        let (offsets, candidates) = locate_with_candidates(&bytes)?;
+
+        Ok(Self {
+            path: None,
+            bytes,
+            lced_offset: offsets.lced,
+            coord_offset: offsets.coords,
+            candidates,
+            dirty: false,
+            allow_ambiguous: false,
+            force_dlc: false,
+            write_facing: false,
+        })
+    }
+
+    /// Path the save file was loaded from (or last saved to), if any
+    #[inline]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Raw bytes of the save file, including any unsaved edits
+    #[inline]
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Consume the save file and return its bytes, including any unsaved edits
+    #[inline]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Offset of the LCED marker
+    #[inline]
+    pub fn lced_offset(&self) -> usize {
+        self.lced_offset
+    }
+
+    /// Offset of the X/Y/Z coordinate block
+    #[inline]
+    pub fn coord_offset(&self) -> usize {
+        self.coord_offset
+    }
+
+    /// Every block after the LCED marker that could hold the coordinates.
+    ///
+    /// The first candidate is the one read and written by this `SaveFile`.
+    #[inline]
+    pub fn coordinate_candidates(&self) -> Vec<CoordinateCandidate> {
+        score_candidates(&self.bytes, &self.candidates)
+    }
+
+    /// Allow writing even if the save has several plausible coordinate blocks.
+    ///
+    /// By default, saving fails with `TeleportError::MultipleCoordinateBlocks`
+    /// in that case, since the edit may have gone to the wrong block.
+    #[inline]
+    pub fn set_allow_ambiguous(&mut self, allow: bool) {
+        self.allow_ambiguous = allow;
+    }
+
+    /// Do not warn about The Old Hunters destinations in saves that show no
+    /// DLC progress.
+    ///
+    /// By default, [`SaveFile::dlc_warning`] reports them, since a game
+    /// without the DLC cannot load the character there.
+    #[inline]
+    pub fn set_force_dlc(&mut self, force: bool) {
+        self.force_dlc = force;
+    }
+
+    /// Write the facing of locations passed to [`SaveFile::teleport_to`].
+    ///
+    /// The facing offset has not been checked against real saves, so by
+    /// default a location's facing is ignored and the current facing is kept.
+    /// [`SaveFile::set_facing`] always writes.
+    #[inline]
+    pub fn set_write_facing(&mut self, write: bool) {
+        self.write_facing = write;
+    }
+
+    /// Whether the character has entered the Hunter's Nightmare or lit any of
+    /// The Old Hunters lanterns.
+    ///
+    /// Fails with `TeleportError::InvalidOffset` if the event flags lie
+    /// outside the file.
+    #[inline]
+    pub fn has_dlc_progress(&self) -> Result<bool, TeleportError> {
+        has_dlc_progress(&self.bytes, self.lced_offset)
+    }
+
+    /// Story progress of the save (evening, night or Blood Moon).
+    ///
+    /// Fails with `TeleportError::InvalidOffset` if the event flags lie
+    /// outside the file. The flags are unverified, so this needs the
+    /// `experimental` feature.
+    #[cfg(feature = "experimental")]
+    #[inline]
+    pub fn world_state(&self) -> Result<WorldState, TeleportError> {
+        world_state(&self.bytes, self.lced_offset)
+    }
+
+    /// Warning for a destination whose map variant does not exist yet in the
+    /// save's world state, or `None` if it does or the state is unreadable
+    #[cfg(feature = "experimental")]
+    #[inline]
+    pub fn world_state_warning(&self, map_id: &[u8; 2]) -> Option<TeleportWarning> {
+        world_state_warning(&self.bytes, self.lced_offset, map_id)
+    }
+
+    /// Warning for a DLC destination in a save that shows no DLC progress or
+    /// whose progress is unreadable, or `None` if the save has progress, the
+    /// warning is forced off, or the destination is not a DLC map
+    #[inline]
+    pub fn dlc_warning(&self, map_id: &[u8; 2]) -> Option<TeleportWarning> {
+        if self.force_dlc {
+            return None;
+        }
+        dlc_warning(&self.bytes, self.lced_offset, map_id)
+    }
+
+    /// Every warning for writing a destination on the given map: see
+    /// [`SaveFile::dlc_warning`] and, with the `experimental` feature,
+    /// `SaveFile::world_state_warning`
+    pub fn destination_warnings(&self, map_id: &[u8; 2]) -> Vec<TeleportWarning> {
+        let warnings = self.dlc_warning(map_id).into_iter();
+        #[cfg(feature = "experimental")]
+        let warnings = warnings.chain(self.world_state_warning(map_id));
+        warnings.collect()
+    }
+
+    /// Name, level and other metadata of the character.
+    ///
+    /// The character offsets have not been checked against real saves, so
+    /// treat the values as a hint. Fields that cannot be read are `None`; see
+    /// [`CharacterInfo`].
+    #[inline]
+    pub fn character(&self) -> CharacterInfo {
+        read_character(&self.bytes, self.lced_offset)
+    }
+
+    /// Which lanterns of the installed catalog are lit.
+    ///
+    /// Fails with `TeleportError::InvalidOffset` if the event flags lie
+    /// outside the file.
+    #[cfg(feature = "experimental")]
+    #[inline]
+    pub fn lantern_statuses(&self) -> Result<Vec<LanternStatus>, TeleportError> {
+        lantern_statuses(
+            &self.bytes,
+            self.lced_offset,
+            catalog::installed_locations(),
+        )
+    }
+
+    /// Warning for a destination whose lantern is not lit yet, or `None` if it
+    /// is lit, has no known event flag, or the flags are unreadable
+    #[cfg(feature = "experimental")]
+    pub fn lantern_warning(&self, location: &Location) -> Option<TeleportWarning> {
+        matches!(self.is_lantern_unlocked(location), Ok(Some(false))).then_some(
+            TeleportWarning::LanternNotLit {
+                location: location.name,
+            },
+        )
+    }
+
+    /// Whether the lantern of a location is lit, or `None` if the location has
+    /// no known event flag
+    #[cfg(feature = "experimental")]
+    pub fn is_lantern_unlocked(&self, location: &Location) -> Result<Option<bool>, TeleportError> {
+        constants::lantern_flags::flag_of(location.name)
+            .map(|flag| read_event_flag(&self.bytes, self.lced_offset, flag))
+            .transpose()
+    }
+
+    /// Current position and map ID
+    #[inline]
+    pub fn position(&self) -> Result<CurrentPosition, TeleportError> {
+        read_position(&self.bytes, self.coord_offset)
+    }
+
+    /// Set the X/Y/Z coordinates
+    pub fn set_position(&mut self, x: f32, y: f32, z: f32) -> Result<(), TeleportError> {
+        write_coordinates(&mut self.bytes, self.coord_offset, [x, y, z])?;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Move the character by the given offsets, keeping the map ID.
+    ///
+    /// Fails with `TeleportError::NonFiniteCoordinate` if an offset, or the
+    /// position it leads to, is not finite.
+    ///
+    /// # Returns
+    ///
+    /// The new position
+    pub fn nudge(&mut self, dx: f32, dy: f32, dz: f32) -> Result<CurrentPosition, TeleportError> {
+        types::ensure_finite(dx, dy, dz)?;
+        let mut position = self.position()?;
+        position.x += dx;
+        position.y += dy;
+        position.z += dz;
+        types::ensure_finite(position.x, position.y, position.z)?;
+        self.set_position(position.x, position.y, position.z)?;
+        Ok(position)
+    }
+
+    /// Direction the character faces, in degrees from 0 up to 360, or `None`
+    /// if the facing lies outside the file or is not a finite angle
+    #[inline]
+    pub fn facing(&self) -> Option<f32> {
+        read_facing(&self.bytes, self.coord_offset)
+    }
+
+    /// Turn the character to face the given direction, in degrees.
+    ///
+    /// Fails with `TeleportError::NonFiniteFacing` for NaN or infinite angles
+    /// and `TeleportError::InvalidOffset` if the facing lies outside the file.
+    pub fn set_facing(&mut self, degrees: f32) -> Result<(), TeleportError> {
+        if !degrees.is_finite() {
+            return Err(TeleportError::NonFiniteFacing(degrees));
+        }
+        write_facing(&mut self.bytes, self.coord_offset, degrees)?;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Current map ID (save file format)
+    #[inline]
+    pub fn map_id(&self) -> Result<[u8; 4], TeleportError> {
+        read_map_id(&self.bytes)
+    }
+
+    /// Set the map ID (save file format)
+    pub fn set_map_id(&mut self, map_id: [u8; 4]) -> Result<(), TeleportError> {
+        write_map_id(&mut self.bytes, map_id)?;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Move the character to the given location (coordinates and map ID, and
+    /// its facing if [`SaveFile::set_write_facing`] is on)
+    pub fn teleport_to(&mut self, location: &Location) -> Result<(), TeleportError> {
+        apply_location(
+            &mut self.bytes,
+            self.coord_offset,
+            location,
+            self.write_facing,
+        )?;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Write back byte ranges recorded by the journal.
+    ///
+    /// Every range is bounds-checked before any is written, so a failed call
+    /// leaves the save untouched.
+    pub(crate) fn restore(&mut self, ranges: &[(usize, Vec<u8>)]) -> Result<(), TeleportError> {
+        for (offset, bytes) in ranges {
+            field_range(&self.bytes, *offset, bytes.len())?;
+        }
+        for (offset, bytes) in ranges {
+            self.bytes[*offset..*offset + bytes.len()].copy_from_slice(bytes);
+        }
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Whether there are edits that have not been saved yet
+    #[inline]
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Write the save file back to its path, taking a backup first.
+    ///
+    /// Uses the default `BackupConfig`. See [`SaveFile::save_with_backups`].
+    #[inline]
+    pub fn save(&mut self) -> Result<Option<BackupInfo>, TeleportError> {
+        self.save_with_backups(&BackupConfig::default())
+    }
+
+    /// Write the save file back to its path with the given backup configuration.
+    ///
+    /// The file is written atomically and read back; if the position in the
+    /// written file does not match, `TeleportError::VerificationFailed` is returned.
+    ///
+    /// # Returns
+    ///
+    /// The backup taken before writing, if backups are enabled
+    ///
+    /// Fails with `TeleportError::NoSavePath` if the save file has no path.
+    pub fn save_with_backups(
+        &mut self,
+        config: &BackupConfig,
+    ) -> Result<Option<BackupInfo>, TeleportError> {
+        let path = self.path.clone().ok_or(TeleportError::NoSavePath)?;
+        self.write_to(&path, config, || ())
+    }
+
+    /// Write the save file back to its path like [`SaveFile::save_with_backups`],
+    /// recording `entry` in the journal as soon as the new contents have
+    /// replaced the file.
+    ///
+    /// The entry is recorded before the file is read back, so an edit that
+    /// reached the disk is journaled even if its verification fails.
+    ///
+    /// # Returns
+    ///
+    /// The backup taken before writing, and a warning if the entry could not
+    /// be recorded
+    pub(crate) fn save_journaled(
+        &mut self,
+        config: &BackupConfig,
+        entry: journal::PendingEntry,
+    ) -> Result<(Option<BackupInfo>, Option<TeleportWarning>), TeleportError> {
+        let path = self.path.clone().ok_or(TeleportError::NoSavePath)?;
+        let mut warning = None;
+        let backup = self.write_to(&path, config, || warning = entry.record(config))?;
+        Ok((backup, warning))
+    }
+
+    /// Write the save file to a new path, which becomes the file's path.
+    ///
+    /// If a file already exists at the target, it is backed up first.
+    pub fn save_as<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<Option<BackupInfo>, TeleportError> {
+        let path = path.as_ref();
+        let backup = self.write_to(path, &BackupConfig::default(), || ())?;
+        self.path = Some(path.to_path_buf());
+        Ok(backup)
+    }
+
+    /// Check that `written` (such as a save file read back after writing)
+    /// holds the position, map ID and facing of this save at the same offset.
+    ///
+    /// Values are compared bit for bit, so a coordinate the save already held
+    /// as NaN still matches.
+    ///
+    /// Fails with `TeleportError::VerificationFailed` or
+    /// `TeleportError::FacingVerificationFailed` if they differ.
+    pub fn verify_written(&self, written: &[u8]) -> Result<(), TeleportError> {
+        let expected = self.position()?;
+        let actual = read_position(written, self.coord_offset)?;
+        let bits = |p: &CurrentPosition| (p.x.to_bits(), p.y.to_bits(), p.z.to_bits(), p.map_id);
+        if bits(&actual) != bits(&expected) {
+            return Err(TeleportError::VerificationFailed { expected, actual });
+        }
+
+        let expected = self.facing();
+        let actual = read_facing(written, self.coord_offset);
+        if actual.map(f32::to_bits) != expected.map(f32::to_bits) {
+            return Err(TeleportError::FacingVerificationFailed { expected, actual });
+        }
+        Ok(())
+    }
+
+    /// Back up, atomically write and verify the file at `path`, calling
+    /// `replaced` once the new contents are on disk
+    fn write_to(
+        &mut self,
+        path: &Path,
+        config: &BackupConfig,
+        replaced: impl FnOnce(),
+    ) -> Result<Option<BackupInfo>, TeleportError> {
+        if !self.allow_ambiguous {
+            ensure_unambiguous(&self.bytes, &self.candidates)?;
+        }
+
+        // Back up whatever is there before replacing it
+        let backup = if config.enabled && path.exists() {
+            Some(backup::create_backup(path, config)?)
+        } else {
+            None
+        };
+
+        atomic::write_atomic(path, &self.bytes)?;
+        replaced();
+
+        // Read it back and make sure the new position and facing actually
+        // landed in the block that was written
+        let written = fs::read(path).map_err(|source| TeleportError::ReadError {
+            path: Some(path.to_path_buf()),
+            source,
+        })?;
+        self.verify_written(&written)?;
+
+        self.dirty = false;
+        Ok(backup)
+    }
+}
+
+// ============================================================================
+// Crate-internal parsing helpers
+// ============================================================================
+
+/// Offsets discovered while parsing a save file
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Offsets {
+    /// Offset of the LCED marker
+    pub lced: usize,
+    /// Offset of the X/Y/Z coordinate block
+    pub coords: usize,
+}
+
+/// Find the LCED marker and the first coordinate block in a save file's bytes.
+///
+/// Scanning stops at the first coordinate block. Both the coordinates and
+/// the map ID are guaranteed to lie inside `bytes` when this succeeds.
+pub(crate) fn locate(bytes: &[u8]) -> Result<Offsets, TeleportError> {
+    let lced = locate_lced(bytes)?;
+    let coords = COORD_PATTERN_FINDER
+        .find(&bytes[lced..])
+        .map(|i| lced + i + constants::COORD_OFFSET_AFTER_PATTERN_BYTES);
+
+    check_offsets(bytes, lced, coords)
+}
+
+/// Like [`locate`], but keep scanning to the end of the file and also return
+/// the offsets of every candidate coordinate block, in file order.
+///
+/// The file is still scanned only once: the LCED search stops at the marker
+/// and the pattern search picks up from there.
+pub(crate) fn locate_with_candidates(bytes: &[u8]) -> Result<(Offsets, Vec<usize>), TeleportError> {
+    let lced = locate_lced(bytes)?;
+    let candidates: Vec<usize> = COORD_PATTERN_FINDER
+        .find_iter(&bytes[lced..])
+        .map(|i| lced + i + constants::COORD_OFFSET_AFTER_PATTERN_BYTES)
+        .collect();
+
+    let offsets = check_offsets(bytes, lced, candidates.first().copied())?;
+    Ok((offsets, candidates))
+}
+
+/// Decode and score the coordinate blocks at the given offsets.
+///
+/// Blocks that do not fit inside the file are skipped.
+pub(crate) fn score_candidates(bytes: &[u8], offsets: &[usize]) -> Vec<CoordinateCandidate> {
+    let bounds = CatalogBounds::of(catalog::installed_locations());
+
+    offsets
+        .iter()
+        .filter_map(|&offset| {
+            let [x, y, z] = read_coordinates(bytes, offset).ok()?;
+            Some(CoordinateCandidate {
+                offset,
+                x,
+                y,
+                z,
+                score: bounds.score([x, y, z]),
+            })
+        })
+        .collect()
+}
+
+/// Fail with `TeleportError::MultipleCoordinateBlocks` if more than one of
+/// the candidate coordinate blocks is plausible
+pub(crate) fn ensure_unambiguous(bytes: &[u8], offsets: &[usize]) -> Result<(), TeleportError> {
+    let plausible: Vec<usize> = score_candidates(bytes, offsets)
+        .iter()
+        .filter(|candidate| candidate.is_plausible())
+        .map(|candidate| candidate.offset)
+        .collect();
+
+    if plausible.len() > 1 {
+        return Err(TeleportError::MultipleCoordinateBlocks { offsets: plausible });
+    }
+    Ok(())
+}
+
+/// Decode the position at `coord_offset` and the map ID
+#[inline]
+pub(crate) fn read_position(
+    bytes: &[u8],
+    coord_offset: usize,
+) -> Result<CurrentPosition, TeleportError> {
+    let [x, y, z] = read_coordinates(bytes, coord_offset)?;
+    Ok(CurrentPosition {
+        x,
+        y,
+        z,
+        map_id: read_map_id(bytes)?,
+    })
+}
+
+/// Decode the character block relative to the LCED marker at `lced`.
+///
+/// The offsets are unverified (see [`constants::PLAYTIME_OFFSET`] and the
+/// fields after it). Fields that lie outside the file or hold implausible
+/// values are `None`.
+pub(crate) fn read_character(bytes: &[u8], lced: usize) -> CharacterInfo {
+    let field = |offset: usize, max: u32| {
+        let value = read_u32(bytes, lced.checked_add(offset)?)?;
+        (value <= max).then_some(value)
+    };
+
+    CharacterInfo {
+        name: lced
+            .checked_add(constants::NAME_OFFSET)
+            .and_then(|offset| read_name(bytes, offset)),
+        level: field(constants::LEVEL_OFFSET, constants::MAX_LEVEL).filter(|&level| level > 0),
+        insight: field(constants::INSIGHT_OFFSET, constants::MAX_INSIGHT),
+        blood_echoes: field(constants::BLOOD_ECHOES_OFFSET, constants::MAX_BLOOD_ECHOES),
+        playtime: field(constants::PLAYTIME_OFFSET, constants::MAX_PLAYTIME_MS)
+            .map(|ms| Duration::from_millis(ms.into())),
+        ng_cycle: field(constants::NG_CYCLE_OFFSET, constants::MAX_NG_CYCLE),
+    }
+}
+
+/// Read a lantern event flag relative to the LCED marker at `lced`
+pub(crate) fn read_event_flag(bytes: &[u8], lced: usize, flag: u32) -> Result<bool, TeleportError> {
+    let (offset, mask) = event_flag_position(lced, flag);
+    let [byte] = read_array(bytes, offset)?;
+    Ok(byte & mask != 0)
+}
+
+/// Whether the DLC entry flag or any DLC lantern flag is set
+pub(crate) fn has_dlc_progress(bytes: &[u8], lced: usize) -> Result<bool, TeleportError> {
+    lantern_flag_block(bytes, lced)?;
+
+    let dlc_lanterns = constants::LOCATIONS
+        .iter()
+        .filter(|location| MapId::from_bytes(location.map_id).is_ok_and(MapId::is_dlc))
+        .filter_map(|location| constants::lantern_flags::flag_of(location.name));
+
+    for flag in std::iter::once(constants::DLC_ENTRY_FLAG).chain(dlc_lanterns) {
+        if read_event_flag(bytes, lced, flag)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Warn if `map_id` is a DLC map and the save shows no DLC progress or its
+/// progress cannot be read. The flags are at unverified positions, so neither
+/// case refuses the teleport.
+pub(crate) fn dlc_warning(bytes: &[u8], lced: usize, map_id: &[u8; 2]) -> Option<TeleportWarning> {
+    let map = MapId::from_bytes(*map_id).ok().filter(|map| map.is_dlc())?;
+    let area = map.name();
+    match has_dlc_progress(bytes, lced) {
+        Ok(true) => None,
+        Ok(false) => Some(TeleportWarning::NoDlcProgress { area }),
+        Err(_) => Some(TeleportWarning::DlcProgressUnknown { area }),
+    }
+}
+
+/// Read the world state from the night and Blood Moon flags
+#[cfg(feature = "experimental")]
+pub(crate) fn world_state(bytes: &[u8], lced: usize) -> Result<WorldState, TeleportError> {
+    if read_event_flag(bytes, lced, constants::BLOOD_MOON_FLAG)? {
+        Ok(WorldState::BloodMoon)
+    } else if read_event_flag(bytes, lced, constants::NIGHT_FLAG)? {
+        Ok(WorldState::Night)
+    } else {
+        Ok(WorldState::Evening)
+    }
+}
+
+/// Warn if `map_id` is a variant that does not exist yet in the save's world
+/// state. Saves whose flags cannot be read get no warning.
+#[cfg(feature = "experimental")]
+pub(crate) fn world_state_warning(
+    bytes: &[u8],
+    lced: usize,
+    map_id: &[u8; 2],
+) -> Option<TeleportWarning> {
+    let required = constants::map_ids::required_world_state(map_id);
+    let actual = world_state(bytes, lced).ok()?;
+    (actual < required).then(|| TeleportWarning::WorldStateMismatch {
+        area: MapId::from_bytes(*map_id).map_or("The map", MapId::name),
+        required,
+        actual,
+    })
+}
+
+/// Report which of `locations` have their lantern lit
+#[cfg(feature = "experimental")]
+pub(crate) fn lantern_statuses(
+    bytes: &[u8],
+    lced: usize,
+    locations: &'static [Location],
+) -> Result<Vec<LanternStatus>, TeleportError> {
+    lantern_flag_block(bytes, lced)?;
+
+    locations
+        .iter()
+        .map(|location| {
+            let unlocked = constants::lantern_flags::flag_of(location.name)
+                .map(|flag| read_event_flag(bytes, lced, flag))
+                .transpose()?;
+            Ok(LanternStatus { location, unlocked })
+        })
+        .collect()
+}
+
+/// Write a location's coordinates, map ID and facing.
+///
+/// Every field is bounds-checked before any is written, so a failed call
+/// leaves `bytes` untouched. The facing is only written if `include_facing`
+/// is set and the location has one.
+#[inline]
+pub(crate) fn apply_location(
+    bytes: &mut [u8],
+    coord_offset: usize,
+    location: &Location,
+    include_facing: bool,
+) -> Result<(), TeleportError> {
+    let facing = location.facing.filter(|_| include_facing);
+    field_range(bytes, coord_offset, constants::COORD_BLOCK_LEN)?;
+    if let Some(degrees) = facing {
+        if !degrees.is_finite() {
+            return Err(TeleportError::NonFiniteFacing(degrees));
+        }
+        field_range(
+            bytes,
+            coord_offset.saturating_add(constants::FACING_OFFSET_AFTER_COORDS),
+            4,
+        )?;
+    }
+    write_map_id(bytes, constants::map_ids::to_save_format(&location.map_id))?;
+    write_coordinates(bytes, coord_offset, [location.x, location.y, location.z])?;
+    if let Some(degrees) = facing {
+        write_facing(bytes, coord_offset, degrees)?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Private helper functions
+// ============================================================================
+
+/// Searcher for the LCED marker, built once per process
+static LCED_FINDER: LazyLock<Finder<'static>> =
+    LazyLock::new(|| Finder::new(&constants::LCED_MARKER_BYTES));
+
+/// Searcher for the pattern preceding the coordinate block
+static COORD_PATTERN_FINDER: LazyLock<Finder<'static>> =
+    LazyLock::new(|| Finder::new(&constants::COORD_PATTERN_BYTES));
+
+/// Check the file size and find the LCED marker
+fn locate_lced(bytes: &[u8]) -> Result<usize, TeleportError> {
+    let len = bytes.len();
+    if len < constants::MIN_SAVE_LEN {
+        return Err(TeleportError::FileTooSmall {
+            len,
+            min: constants::MIN_SAVE_LEN,
+        });
+    }
+
+    match LCED_FINDER.find(bytes) {
+        Some(lced) => Ok(lced),
+        None if looks_encrypted(bytes) => Err(TeleportError::ProbablyEncrypted),
+        None => Err(TeleportError::LcedMarkerNotFound { scanned: 0..len }),
+    }
+}
+
+/// Turn the result of a coordinate search into checked offsets
+fn check_offsets(
+    bytes: &[u8],
+    lced: usize,
+    coords: Option<usize>,
+) -> Result<Offsets, TeleportError> {
+    let coords = coords.ok_or(TeleportError::CoordPatternNotFound {
+        scanned: lced..bytes.len(),
+    })?;
+
+    // Both the coordinates and the map ID must fit inside the file
+    field_range(bytes, coords, constants::COORD_BLOCK_LEN)?;
+    field_range(bytes, constants::MAP_ID_OFFSET, 4)?;
+
+    Ok(Offsets { lced, coords })
+}
+
+/// Per-axis range spanned by a set of locations, used to score candidates
+struct CatalogBounds {
+    min: [f32; 3],
+    max: [f32; 3],
+}
+
+impl CatalogBounds {
+    fn of(locations: &[Location]) -> Self {
+        let mut bounds = Self {
+            min: [f32::INFINITY; 3],
+            max: [f32::NEG_INFINITY; 3],
+        };
+        for location in locations {
+            for (axis, value) in [location.x, location.y, location.z].into_iter().enumerate() {
+                bounds.min[axis] = bounds.min[axis].min(value);
+                bounds.max[axis] = bounds.max[axis].max(value);
+            }
+        }
+        bounds
+    }
+
+    /// Fraction of axes that lie within the range (plus a margin). A block
+    /// with a non-finite value, or one that is all zeros, scores 0.
+    fn score(&self, coords: [f32; 3]) -> f32 {
+        if coords.iter().any(|v| !v.is_finite()) || coords.iter().all(|&v| v == 0.0) {
+            return 0.0;
+        }
+
+        let in_range = coords
+            .into_iter()
+            .enumerate()
+            .filter(|&(axis, value)| {
+                value >= self.min[axis] - constants::PLAUSIBLE_COORD_MARGIN
+                    && value <= self.max[axis] + constants::PLAUSIBLE_COORD_MARGIN
+            })
+            .count();
+        in_range as f32 / 3.0
+    }
+}
+
+/// Whether bytes without an LCED marker look like encrypted data
+fn looks_encrypted(bytes: &[u8]) -> bool {
+    let zeros = memchr::memchr_iter(0, bytes).count();
+    zeros * constants::ENCRYPTED_ZERO_BYTE_RATIO < bytes.len()
+}
+
+/// Check that `len` bytes starting at `offset` lie inside `bytes`
+#[inline]
+fn field_range(bytes: &[u8], offset: usize, len: usize) -> Result<Range<usize>, TeleportError> {
+    let invalid = || TeleportError::InvalidOffset {
+        range: offset..offset.saturating_add(len),
+        len: bytes.len(),
+    };
+
+    let end = offset.checked_add(len).ok_or_else(invalid)?;
+    if end > bytes.len() {
+        return Err(invalid());
+    }
+    Ok(offset..end)
+}
+
+#[inline]
+fn read_array<const N: usize>(bytes: &[u8], offset: usize) -> Result<[u8; N], TeleportError> {
+    let range = field_range(bytes, offset, N)?;
+    let mut buf = [0u8; N];
+    buf.copy_from_slice(&bytes[range]);
+    Ok(buf)
+}
+
+#[inline]
+fn write_array<const N: usize>(
+    bytes: &mut [u8],
+    offset: usize,
+    value: [u8; N],
+) -> Result<(), TeleportError> {
+    let range = field_range(bytes, offset, N)?;
+    bytes[range].copy_from_slice(&value);
+    Ok(())
+}
+
+#[inline]
+fn read_coordinates(bytes: &[u8], offset: usize) -> Result<[f32; 3], TeleportError> {
+    let block: [u8; constants::COORD_BLOCK_LEN] = read_array(bytes, offset)?;
+    let read =
+        |at: usize| f32::from_le_bytes([block[at], block[at + 1], block[at + 2], block[at + 3]]);
+    Ok([read(0), read(4), read(8)])
+}
+
+/// Check that the lantern flag block lies inside the file
+#[inline]
+fn lantern_flag_block(bytes: &[u8], lced: usize) -> Result<Range<usize>, TeleportError> {
+    field_range(
+        bytes,
+        lced.saturating_add(constants::LANTERN_FLAGS_OFFSET),
+        constants::LANTERN_FLAGS_LEN,
+    )
+}
+
+/// Byte offset and bit mask of an event flag, most significant bit first
+#[inline]
+fn event_flag_position(lced: usize, flag: u32) -> (usize, u8) {
+    let byte = constants::LANTERN_FLAGS_OFFSET + flag as usize / 8;
+    (lced.saturating_add(byte), 0x80 >> (flag % 8))
+}
+
+#[inline]
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    read_array(bytes, offset).ok().map(u32::from_le_bytes)
+}
+
+/// Decode a NUL-padded UTF-16LE name. Empty names, invalid UTF-16 and
+/// control characters mean the bytes are not a name.
+fn read_name(bytes: &[u8], offset: usize) -> Option<String> {
+    let raw: [u8; constants::NAME_MAX_UNITS * 2] = read_array(bytes, offset).ok()?;
+    let units: Vec<u16> = raw
+        .chunks_exact(2)
+        .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+        .take_while(|&unit| unit != 0)
+        .collect();
+
+    let name = String::from_utf16(&units).ok()?;
+    if name.trim().is_empty() || name.chars().any(char::is_control) {
+        return None;
+    }
+    Some(name)
+}
+
+#[inline]
+fn read_map_id(bytes: &[u8]) -> Result<[u8; 4], TeleportError> {
+    read_array(bytes, constants::MAP_ID_OFFSET)
+}
+
+#[inline]
+fn write_map_id(bytes: &mut [u8], map_id: [u8; 4]) -> Result<(), TeleportError> {
+    write_array(bytes, constants::MAP_ID_OFFSET, map_id)
+}
+
+/// Read the yaw after the coordinate block as degrees in `0.0..360.0`
+#[inline]
+fn read_facing(bytes: &[u8], coord_offset: usize) -> Option<f32> {
+    let offset = coord_offset.saturating_add(constants::FACING_OFFSET_AFTER_COORDS);
+    let radians = f32::from_le_bytes(read_array(bytes, offset).ok()?);
+    radians
+        .is_finite()
+        .then(|| radians.to_degrees().rem_euclid(360.0))
+}
+
+/// Write a facing in degrees as a yaw in radians between -π and π
+#[inline]
+fn write_facing(bytes: &mut [u8], coord_offset: usize, degrees: f32) -> Result<(), TeleportError> {
+    let offset = coord_offset.saturating_add(constants::FACING_OFFSET_AFTER_COORDS);
+    let mut yaw = degrees.rem_euclid(360.0);
+    if yaw > 180.0 {
+        yaw -= 360.0;
+    }
+    write_array(bytes, offset, yaw.to_radians().to_le_bytes())
+}
+
+#[inline]
+fn write_coordinates(
+    bytes: &mut [u8],
+    offset: usize,
+    [x, y, z]: [f32; 3],
+) -> Result<(), TeleportError> {
+    let mut block = [0u8; constants::COORD_BLOCK_LEN];
+    block[0..4].copy_from_slice(&x.to_le_bytes());
+    block[4..8].copy_from_slice(&y.to_le_bytes());
+    block[8..12].copy_from_slice(&z.to_le_bytes());
+    write_array(bytes, offset, block)
+}
@@ -0,0 +1,89 @@
+//! Character slots: the `userdata00NN` files of a save directory
+//!
+//! Each character lives in its own save file, `userdata0000` for the first
+//! character, `userdata0001` for the second and so on. Scanning a directory
+//! validates every slot file found in it.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::constants;
+use crate::save_file::SaveFile;
+use crate::types::{CharacterInfo, CurrentPosition, PositionInfo, TeleportError};
+
+/// A character slot found in a save directory
+#[derive(Debug)]
+pub struct SaveSlot {
+    /// Slot index (0 for `userdata0000`)
+    pub index: usize,
+    /// Path to the slot's save file
+    pub path: PathBuf,
+    /// The character's position, or why the file could not be read
+    pub position: Result<CurrentPosition, TeleportError>,
+    /// Map area and nearest location of the position, if it is known
+    pub info: Option<PositionInfo>,
+    /// Name, level and other metadata of the character, if the file is valid
+    pub character: Option<CharacterInfo>,
+}
+
+impl SaveSlot {
+    /// Whether the slot's save file is valid
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.position.is_ok()
+    }
+}
+
+/// Scan a directory for character slots and validate each one.
+///
+/// Only files named `userdata` followed by four digits are considered. Slots
+/// are returned in index order; an invalid slot is returned with its error
+/// rather than failing the whole scan.
+///
+/// # Arguments
+///
+/// * `dir` - The save directory
+///
+/// # Returns
+///
+/// All slots found, or `TeleportError::ReadError` if the directory cannot be read
+pub fn scan_slots<P: AsRef<Path>>(dir: P) -> Result<Vec<SaveSlot>, TeleportError> {
+    let dir = dir.as_ref();
+    let entries = fs::read_dir(dir).map_err(|source| TeleportError::ReadError {
+        path: Some(dir.to_path_buf()),
+        source,
+    })?;
+
+    let mut slots: Vec<SaveSlot> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| {
+            let index = slot_index(&entry.file_name().to_string_lossy())?;
+            let path = entry.path();
+            let (position, character) = match SaveFile::load(&path) {
+                Ok(save) => (save.position(), Some(save.character())),
+                Err(e) => (Err(e), None),
+            };
+            let info = position.as_ref().ok().and_then(crate::identify_position);
+            Some(SaveSlot {
+                index,
+                path,
+                position,
+                info,
+                character,
+            })
+        })
+        .collect();
+
+    slots.sort_by_key(|slot| slot.index);
+    Ok(slots)
+}
+
+/// Parse the slot index out of a save file name
+fn slot_index(file_name: &str) -> Option<usize> {
+    let digits = file_name.strip_prefix(constants::SLOT_FILE_PREFIX)?;
+    if digits.len() != constants::SLOT_INDEX_DIGITS || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse().ok()
+}
@@ -1,7 +1,20 @@
 //! Type definitions for lantern teleport functionality
 
+use std::fmt;
+use std::io;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::constants;
+use crate::map_id::MapId;
+
 /// Represents a teleport destination in Bloodborne
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Location {
     /// Display name of the location
     pub name: &'static str,
@@ -15,10 +28,121 @@ pub struct Location {
     pub z: f32,
     /// Map ID as a 2-byte array
     pub map_id: [u8; 2],
+    /// Direction the character faces on arrival, in degrees around the
+    /// vertical axis, or `None` to keep the current facing
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub facing: Option<f32>,
+}
+
+impl Location {
+    /// Create a location from arbitrary coordinates and a map ID.
+    ///
+    /// This allows teleporting to spots that are not in the catalog, such as
+    /// boss doors or item locations.
+    ///
+    /// # Arguments
+    ///
+    /// * `x`, `y`, `z` - Destination coordinates, which must be finite
+    /// * `map_id` - Destination map ID, which must be a known map ID
+    ///
+    /// # Returns
+    ///
+    /// The location, or `TeleportError::NonFiniteCoordinate` /
+    /// `TeleportError::UnknownMapId` if the input is invalid
+    pub fn custom(x: f32, y: f32, z: f32, map_id: [u8; 2]) -> Result<Self, TeleportError> {
+        ensure_finite(x, y, z)?;
+
+        MapId::from_bytes(map_id)?;
+
+        Ok(Self {
+            name: constants::CUSTOM_LOCATION_NAME,
+            region: constants::CUSTOM_LOCATION_REGION,
+            x,
+            y,
+            z,
+            map_id,
+            facing: None,
+        })
+    }
+
+    /// The same location with a different facing, in degrees.
+    ///
+    /// Fails with `TeleportError::NonFiniteFacing` if the angle is NaN or
+    /// infinite.
+    pub fn with_facing(self, degrees: f32) -> Result<Self, TeleportError> {
+        if !degrees.is_finite() {
+            return Err(TeleportError::NonFiniteFacing(degrees));
+        }
+        Ok(Self {
+            facing: Some(degrees),
+            ..self
+        })
+    }
+}
+
+/// Owned variant of [`Location`], for locations that do not live in the
+/// compiled catalog (catalog files, JSON output, IPC)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
+pub struct OwnedLocation {
+    /// Display name of the location
+    pub name: String,
+    /// Region where this location belongs
+    pub region: String,
+    /// X coordinate
+    pub x: f32,
+    /// Y coordinate
+    pub y: f32,
+    /// Z coordinate
+    pub z: f32,
+    /// Map ID as a 2-byte array
+    pub map_id: [u8; 2],
+    /// Direction the character faces on arrival, in degrees
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub facing: Option<f32>,
+}
+
+impl From<&Location> for OwnedLocation {
+    fn from(location: &Location) -> Self {
+        Self {
+            name: location.name.to_string(),
+            region: location.region.to_string(),
+            x: location.x,
+            y: location.y,
+            z: location.z,
+            map_id: location.map_id,
+            facing: location.facing,
+        }
+    }
+}
+
+/// A headstone region and the locations that belong to it
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Region {
+    /// Name of the region
+    pub name: String,
+    /// Locations in this region, in catalog order
+    pub locations: Vec<OwnedLocation>,
+}
+
+/// Check that all three coordinates are finite
+pub(crate) fn ensure_finite(x: f32, y: f32, z: f32) -> Result<(), TeleportError> {
+    for (axis, value) in [('X', x), ('Y', y), ('Z', z)] {
+        if !value.is_finite() {
+            return Err(TeleportError::NonFiniteCoordinate { axis, value });
+        }
+    }
+    Ok(())
 }
 
 /// Current position in the game world extracted from a save file
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CurrentPosition {
     /// X coordinate
     pub x: f32,
@@ -30,17 +154,547 @@ pub struct CurrentPosition {
     pub map_id: [u8; 4],
 }
 
+impl CurrentPosition {
+    /// The typed map ID of this position.
+    ///
+    /// Fails with `TeleportError::UnknownMapId` or `TeleportError::InvalidMapId`
+    /// if the save contains a map ID this crate does not know about.
+    #[inline]
+    pub fn map(&self) -> Result<MapId, TeleportError> {
+        MapId::from_save_format(self.map_id)
+    }
+}
+
+/// A catalog location close to a position
+#[derive(Debug, Clone, PartialEq)]
+pub struct NearbyLocation {
+    /// The catalog location
+    pub location: &'static Location,
+    /// Straight-line distance from the position, in game units
+    pub distance: f32,
+}
+
+/// What is known about where a position lies in the game world
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionInfo {
+    /// The map the position is on
+    pub map: MapId,
+    /// The nearest catalog location on the same map, if the map has any
+    pub nearest: Option<NearbyLocation>,
+}
+
+/// Character metadata extracted from a save file.
+///
+/// Each field is `None` if it lies outside the file or holds a value the
+/// game cannot produce, so a partly unreadable save still yields the rest.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CharacterInfo {
+    /// Character name
+    pub name: Option<String>,
+    /// Character level
+    pub level: Option<u32>,
+    /// Insight
+    pub insight: Option<u32>,
+    /// Blood echoes held
+    pub blood_echoes: Option<u32>,
+    /// Total play time
+    pub playtime: Option<Duration>,
+    /// NG+ cycle (0 for the first playthrough)
+    pub ng_cycle: Option<u32>,
+}
+
+/// Whether a catalog lantern has been lit in a save
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanternStatus {
+    /// The catalog location
+    pub location: &'static Location,
+    /// Whether its lantern is lit, or `None` if the location has no known
+    /// event flag (such as entries from catalog files)
+    pub unlocked: Option<bool>,
+}
+
+impl LanternStatus {
+    /// Whether the lantern is known not to be lit yet
+    #[inline]
+    pub fn is_locked(&self) -> bool {
+        self.unlocked == Some(false)
+    }
+}
+
+/// Story progress of a save, which decides the map variants that exist
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WorldState {
+    /// The first hunt, before night falls
+    Evening,
+    /// Night has fallen
+    Night,
+    /// The Blood Moon has risen
+    BloodMoon,
+}
+
+impl fmt::Display for WorldState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Evening => "Evening",
+            Self::Night => "Night",
+            Self::BloodMoon => "Blood Moon",
+        })
+    }
+}
+
+/// Configuration for the automatic backup store
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackupConfig {
+    /// Whether a backup is taken before the save file is written
+    pub enabled: bool,
+    /// Directory to store backups in (defaults to a directory next to the save file)
+    pub directory: Option<PathBuf>,
+    /// Maximum number of backups kept per save file (`None` keeps all of them)
+    pub max_backups: Option<usize>,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            directory: None,
+            max_backups: Some(constants::DEFAULT_MAX_BACKUPS),
+        }
+    }
+}
+
+/// A single backup of a save file
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackupInfo {
+    /// Path to the backup file
+    pub path: PathBuf,
+    /// When the backup was created
+    pub created: SystemTime,
+    /// Size of the backup in bytes
+    pub size: u64,
+}
+
+/// A block in a save file that could hold the player's coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoordinateCandidate {
+    /// Offset of the X/Y/Z block
+    pub offset: usize,
+    /// X coordinate decoded at the offset
+    pub x: f32,
+    /// Y coordinate decoded at the offset
+    pub y: f32,
+    /// Z coordinate decoded at the offset
+    pub z: f32,
+    /// How likely the block holds a real position, from 0.0 (garbage) to 1.0
+    /// (finite and within the range of the known locations)
+    pub score: f32,
+}
+
+impl CoordinateCandidate {
+    /// Whether the candidate looks like a real position
+    #[inline]
+    pub fn is_plausible(&self) -> bool {
+        self.score >= 1.0
+    }
+}
+
+/// Options controlling how a teleport is performed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TeleportOptions {
+    /// Backup behaviour before the save file is modified
+    pub backups: BackupConfig,
+    /// Write to the first coordinate block even if the save has several
+    /// plausible ones (see [`crate::scan_coordinate_candidates`])
+    pub allow_ambiguous: bool,
+    /// Do not warn about The Old Hunters destinations in saves that show no
+    /// DLC progress (see [`crate::SaveFile::has_dlc_progress`])
+    pub force_dlc: bool,
+    /// Write the destination's facing (see [`crate::SaveFile::set_write_facing`]);
+    /// the current facing is kept otherwise
+    pub write_facing: bool,
+}
+
+/// Result of a successful teleport or other edit of a save file
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeleportOutcome {
+    /// The backup taken before the save file was modified, if any
+    pub backup: Option<BackupInfo>,
+    /// Problems the game may have with the written save
+    pub warnings: Vec<TeleportWarning>,
+}
+
+/// A teleport that was written but may not work out in the game
+#[derive(Debug, Clone, PartialEq)]
+pub enum TeleportWarning {
+    /// The destination map variant does not exist yet in the save's world
+    /// state
+    #[cfg(feature = "experimental")]
+    WorldStateMismatch {
+        /// Area name of the destination map
+        area: &'static str,
+        /// Earliest world state in which the map variant exists
+        required: WorldState,
+        /// World state of the save
+        actual: WorldState,
+    },
+    /// The destination is in The Old Hunters DLC, but the save shows no DLC
+    /// progress
+    NoDlcProgress {
+        /// Area name of the destination map
+        area: &'static str,
+    },
+    /// The destination is in The Old Hunters DLC, but the save's DLC progress
+    /// could not be read, so it was written without checking
+    DlcProgressUnknown {
+        /// Area name of the destination map
+        area: &'static str,
+    },
+    /// The destination's lantern is not lit in the save yet
+    #[cfg(feature = "experimental")]
+    LanternNotLit {
+        /// Name of the destination
+        location: &'static str,
+    },
+    /// The edit was written but could not be recorded in the journal, so it
+    /// cannot be undone
+    NotJournaled(String),
+}
+
+impl fmt::Display for TeleportWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "experimental")]
+            Self::WorldStateMismatch {
+                area,
+                required,
+                actual,
+            } => write!(
+                f,
+                "{} is only loaded from {} on, but this save is still at {}; the character may \
+                 fall out of the world",
+                area, required, actual
+            ),
+            Self::NoDlcProgress { area } => write!(
+                f,
+                "{} is in The Old Hunters DLC, but this save has not entered the Hunter's \
+                 Nightmare or lit any of its lanterns; loading it there without the DLC \
+                 installed will fail",
+                area
+            ),
+            Self::DlcProgressUnknown { area } => write!(
+                f,
+                "{} is in The Old Hunters DLC, but this save's DLC progress could not be read; \
+                 loading it there without the DLC installed will fail",
+                area
+            ),
+            #[cfg(feature = "experimental")]
+            Self::LanternNotLit { location } => write!(
+                f,
+                "the {} lantern is not lit in this save yet; the area may be inaccessible or \
+                 break quest progress",
+                location
+            ),
+            Self::NotJournaled(reason) => write!(
+                f,
+                "the save was written but the teleport journal was not, so this edit cannot \
+                 be undone: {}",
+                reason
+            ),
+        }
+    }
+}
+
 /// Error type for teleport operations
 #[derive(Debug)]
 pub enum TeleportError {
     /// Failed to read the save file
-    ReadError(String),
+    ReadError {
+        /// File that could not be read (`None` for readers and streams)
+        path: Option<PathBuf>,
+        /// The underlying I/O error
+        source: io::Error,
+    },
     /// Failed to write the save file
-    WriteError(String),
+    WriteError {
+        /// File that could not be written (`None` for streams)
+        path: Option<PathBuf>,
+        /// The underlying I/O error
+        source: io::Error,
+    },
+    /// A save file loaded from memory has no path to be written back to
+    NoSavePath,
+    /// The path does not name a file
+    InvalidPath(PathBuf),
+    /// The file is smaller than the smallest possible save file
+    FileTooSmall {
+        /// Size of the file in bytes
+        len: usize,
+        /// Minimum size of a save file in bytes
+        min: usize,
+    },
+    /// The file has no LCED marker and looks like random data, which is what
+    /// an encrypted save looks like
+    ProbablyEncrypted,
     /// Could not find LCED marker in save file
-    LcedMarkerNotFound,
+    LcedMarkerNotFound {
+        /// Byte range that was searched
+        scanned: Range<usize>,
+    },
     /// Could not find coordinate pattern in save file
-    CoordPatternNotFound,
-    /// Invalid coordinate offset
-    InvalidOffset,
+    CoordPatternNotFound {
+        /// Byte range that was searched
+        scanned: Range<usize>,
+    },
+    /// More than one plausible coordinate block matches, so it is unclear
+    /// which one holds the player position
+    MultipleCoordinateBlocks {
+        /// Offsets of every plausible coordinate block
+        offsets: Vec<usize>,
+    },
+    /// A field lies (partly) outside the file
+    InvalidOffset {
+        /// Byte range of the field
+        range: Range<usize>,
+        /// Size of the file in bytes
+        len: usize,
+    },
+    /// Failed to create, list or prune backups
+    BackupError {
+        /// Backup file or directory involved
+        path: PathBuf,
+        /// The underlying I/O error
+        source: io::Error,
+    },
+    /// A coordinate is NaN or infinite
+    NonFiniteCoordinate {
+        /// Axis of the offending coordinate ('X', 'Y' or 'Z')
+        axis: char,
+        /// The offending value
+        value: f32,
+    },
+    /// The text is not a coordinate triple
+    InvalidCoordinates(String),
+    /// A facing angle is NaN or infinite
+    NonFiniteFacing(f32),
+    /// The map ID is not one of the known map IDs
+    UnknownMapId([u8; 2]),
+    /// The text or 4-byte value is not a map ID at all
+    InvalidMapId(String),
+    /// A catalog file could not be read, parsed or failed schema validation
+    CatalogError {
+        /// Catalog file or directory involved (`None` when installing)
+        path: Option<PathBuf>,
+        /// What was wrong with it
+        message: String,
+        /// The underlying I/O or parse error, if any
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+    /// A catalog file defines a location whose name is already taken
+    DuplicateLocation(String),
+    /// Failed to read, parse or write the bookmarks file
+    BookmarkError {
+        /// Bookmarks file or directory involved, if any
+        path: Option<PathBuf>,
+        /// What was wrong with it
+        message: String,
+        /// The underlying I/O or TOML error, if any
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+    /// No bookmark with the given name exists
+    BookmarkNotFound(String),
+    /// A bookmark with the given name already exists
+    BookmarkExists(String),
+    /// Failed to read, parse or write the teleport journal
+    JournalError {
+        /// Journal file involved, if any
+        path: Option<PathBuf>,
+        /// What was wrong with it
+        message: String,
+        /// The underlying I/O error, if any
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+    /// The journal has no teleport left to undo
+    NothingToUndo,
+    /// The save file has changed since the last edit recorded in its journal
+    JournalMismatch(PathBuf),
+    /// The position read back after writing does not match the requested one
+    VerificationFailed {
+        /// Position that was written
+        expected: CurrentPosition,
+        /// Position found in the file after writing
+        actual: CurrentPosition,
+    },
+    /// The facing read back after writing does not match the requested one
+    FacingVerificationFailed {
+        /// Facing that was written, in degrees
+        expected: Option<f32>,
+        /// Facing found in the file after writing, in degrees
+        actual: Option<f32>,
+    },
+}
+
+impl fmt::Display for TeleportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ReadError {
+                path: Some(path),
+                source,
+            } => write!(f, "failed to read {}: {}", path.display(), source),
+            Self::ReadError { path: None, source } => {
+                write!(f, "failed to read save data: {}", source)
+            }
+            Self::WriteError {
+                path: Some(path),
+                source,
+            } => write!(f, "failed to write {}: {}", path.display(), source),
+            Self::WriteError { path: None, source } => {
+                write!(f, "failed to write save data: {}", source)
+            }
+            Self::NoSavePath => write!(
+                f,
+                "save file was loaded from memory and has no path; use save_as"
+            ),
+            Self::InvalidPath(path) => write!(f, "{} does not name a file", path.display()),
+            Self::FileTooSmall { len, min } => write!(
+                f,
+                "file is only {} bytes but a save file needs at least {}; \
+                 make sure you picked a userdata file",
+                len, min
+            ),
+            Self::ProbablyEncrypted => write!(
+                f,
+                "file looks encrypted; decrypt the save before using this tool"
+            ),
+            Self::LcedMarkerNotFound { scanned } => write!(
+                f,
+                "no LCED marker in bytes {:#X}..{:#X}; this is not a decrypted Bloodborne save",
+                scanned.start, scanned.end
+            ),
+            Self::CoordPatternNotFound { scanned } => write!(
+                f,
+                "no coordinate block in bytes {:#X}..{:#X}; the save may be from an unsupported game version",
+                scanned.start, scanned.end
+            ),
+            Self::MultipleCoordinateBlocks { offsets } => {
+                let offsets: Vec<String> = offsets.iter().map(|o| format!("{:#X}", o)).collect();
+                write!(
+                    f,
+                    "found {} plausible coordinate blocks (at {}); refusing to guess which one holds \
+                     the position (allow ambiguous writes to use the first one)",
+                    offsets.len(),
+                    offsets.join(", ")
+                )
+            }
+            Self::InvalidOffset { range, len } => write!(
+                f,
+                "bytes {:#X}..{:#X} lie outside the {}-byte file; the save is truncated",
+                range.start, range.end, len
+            ),
+            Self::BackupError { path, source } => {
+                write!(f, "backup failed for {}: {}", path.display(), source)
+            }
+            Self::NonFiniteCoordinate { axis, value } => {
+                write!(f, "{} coordinate must be finite, got {}", axis, value)
+            }
+            Self::InvalidCoordinates(text) => {
+                write!(f, "'{}' is not a coordinate triple (expected X,Y,Z)", text)
+            }
+            Self::NonFiniteFacing(value) => {
+                write!(f, "facing must be a finite angle in degrees, got {}", value)
+            }
+            Self::UnknownMapId([variant, area]) => {
+                write!(f, "unknown map ID {:02X}:{:02X}", variant, area)
+            }
+            Self::InvalidMapId(text) => write!(f, "'{}' is not a map ID (expected AA:BB)", text),
+            Self::CatalogError {
+                path,
+                message,
+                source,
+            } => write_file_error(f, "catalog", path, message, source),
+            Self::DuplicateLocation(name) => {
+                write!(f, "a location named '{}' already exists", name)
+            }
+            Self::BookmarkError {
+                path,
+                message,
+                source,
+            } => write_file_error(f, "bookmark", path, message, source),
+            Self::BookmarkNotFound(name) => write!(f, "no bookmark named '{}'", name),
+            Self::BookmarkExists(name) => write!(f, "a bookmark named '{}' already exists", name),
+            Self::JournalError {
+                path,
+                message,
+                source,
+            } => write_file_error(f, "journal", path, message, source),
+            Self::NothingToUndo => write!(f, "the journal has no teleport left to undo"),
+            Self::JournalMismatch(path) => write!(
+                f,
+                "{} has changed since the last teleport recorded in its journal; refusing to \
+                 undo (restore a backup instead)",
+                path.display()
+            ),
+            Self::VerificationFailed { expected, actual } => write!(
+                f,
+                "verification failed: wrote X: {:.2}, Y: {:.2}, Z: {:.2} but read back X: {:.2}, Y: {:.2}, Z: {:.2}",
+                expected.x, expected.y, expected.z, actual.x, actual.y, actual.z
+            ),
+            Self::FacingVerificationFailed { expected, actual } => {
+                let describe = |facing: &Option<f32>| match facing {
+                    Some(degrees) => format!("{:.0}°", degrees),
+                    None => "no facing".to_string(),
+                };
+                write!(
+                    f,
+                    "verification failed: wrote facing {} but read back {}",
+                    describe(expected),
+                    describe(actual)
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for TeleportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ReadError { source, .. }
+            | Self::WriteError { source, .. }
+            | Self::BackupError { source, .. } => Some(source),
+            Self::CatalogError {
+                source: Some(source),
+                ..
+            }
+            | Self::BookmarkError {
+                source: Some(source),
+                ..
+            }
+            | Self::JournalError {
+                source: Some(source),
+                ..
+            } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Format a catalog, bookmark or journal error as
+/// "`kind` error: `path`: `message`: `source`", leaving out missing parts
+fn write_file_error(
+    f: &mut fmt::Formatter<'_>,
+    kind: &str,
+    path: &Option<PathBuf>,
+    message: &str,
+    source: &Option<Box<dyn std::error::Error + Send + Sync>>,
+) -> fmt::Result {
+    write!(f, "{} error: ", kind)?;
+    if let Some(path) = path {
+        write!(f, "{}: ", path.display())?;
+    }
+    f.write_str(message)?;
+    if let Some(source) = source {
+        write!(f, ": {}", source)?;
+    }
+    Ok(())
 }
@@ -0,0 +1,111 @@
+//! Atomic writes and the read-back verification of saved files
+
+use lantern_teleport_core::{BackupConfig, SaveFile, TeleportError};
+
+mod common;
+use common::{COORDS, Scratch, put_f32s, save};
+
+/// File names in the directory, sorted
+fn entries(scratch: &Scratch) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(&scratch.dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+    names
+}
+
+fn no_backups() -> BackupConfig {
+    BackupConfig {
+        enabled: false,
+        ..BackupConfig::default()
+    }
+}
+
+#[test]
+fn saves_replace_the_file_without_leaving_a_temporary_file() {
+    let scratch = Scratch::new("replaced");
+    let path = scratch.write("userdata0000", &save());
+
+    let mut save = SaveFile::load(&path).unwrap();
+    save.set_position(1.0, 2.0, 3.0).unwrap();
+    save.save_with_backups(&no_backups()).unwrap();
+
+    assert_eq!(std::fs::read(&path).unwrap(), save.bytes());
+    assert!(!save.is_dirty());
+    assert_eq!(entries(&scratch), vec!["userdata0000"]);
+}
+
+#[test]
+fn failed_renames_remove_the_temporary_file() {
+    let scratch = Scratch::new("failed");
+    let path = scratch.write("userdata0000", &save());
+    let mut save = SaveFile::load(&path).unwrap();
+    save.set_position(1.0, 2.0, 3.0).unwrap();
+
+    // A non-empty directory in place of the save cannot be renamed over
+    std::fs::remove_file(&path).unwrap();
+    std::fs::create_dir(&path).unwrap();
+    std::fs::write(path.join("keep"), b"").unwrap();
+
+    assert!(matches!(
+        save.save_with_backups(&no_backups()),
+        Err(TeleportError::WriteError { .. })
+    ));
+    assert!(save.is_dirty());
+    assert!(path.join("keep").exists());
+    assert_eq!(entries(&scratch), vec!["userdata0000"]);
+}
+
+#[test]
+fn read_back_positions_must_match() {
+    let save = SaveFile::from_bytes(save()).unwrap();
+    assert!(save.verify_written(save.bytes()).is_ok());
+
+    let mut moved = save.bytes().to_vec();
+    put_f32s(&mut moved, COORDS + 4, &[1.0]);
+    match save.verify_written(&moved) {
+        Err(TeleportError::VerificationFailed { expected, actual }) => {
+            assert_eq!(expected.y, 0.0);
+            assert_eq!(actual.y, 1.0);
+        }
+        other => panic!("expected a verification failure, got {:?}", other),
+    }
+
+    let mut remapped = save.bytes().to_vec();
+    remapped[0x07] = 0x18;
+    assert!(matches!(
+        save.verify_written(&remapped),
+        Err(TeleportError::VerificationFailed { .. })
+    ));
+}
+
+#[test]
+fn read_back_facings_must_match() {
+    let mut save = SaveFile::from_bytes(save()).unwrap();
+    save.set_facing(90.0).unwrap();
+
+    let mut turned = save.bytes().to_vec();
+    put_f32s(&mut turned, COORDS + 12, &[0.0]);
+    assert!(matches!(
+        save.verify_written(&turned),
+        Err(TeleportError::FacingVerificationFailed {
+            expected: Some(_),
+            actual: Some(_),
+        })
+    ));
+}
+
+#[test]
+fn saves_holding_nan_coordinates_still_verify() {
+    let scratch = Scratch::new("nan");
+    let mut bytes = save();
+    put_f32s(&mut bytes, COORDS, &[f32::NAN, 0.0, 0.0]);
+    let path = scratch.write("userdata0000", &bytes);
+
+    let mut save = SaveFile::load(&path).unwrap();
+    save.set_facing(90.0).unwrap();
+    save.save_with_backups(&no_backups()).unwrap();
+
+    assert!(save.verify_written(&std::fs::read(&path).unwrap()).is_ok());
+}
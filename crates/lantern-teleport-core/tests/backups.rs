@@ -0,0 +1,95 @@
+//! Creating, pruning and restoring backups
+
+use lantern_teleport_core::{
+    BackupConfig, TeleportError, create_backup, list_backups, restore_backup,
+};
+
+mod common;
+use common::{COORDS, Scratch, put_f32s, save};
+
+/// A backup config keeping at most `max_backups` in a directory of `scratch`
+fn config(scratch: &Scratch, max_backups: Option<usize>) -> BackupConfig {
+    BackupConfig {
+        enabled: true,
+        directory: Some(scratch.dir.join("backups")),
+        max_backups,
+    }
+}
+
+/// [`save`] with the character standing at `x`
+fn save_at(x: f32) -> Vec<u8> {
+    let mut bytes = save();
+    put_f32s(&mut bytes, COORDS, &[x, 0.0, 0.0]);
+    bytes
+}
+
+#[test]
+fn backups_copy_the_save_and_are_listed_newest_first() {
+    let scratch = Scratch::new("listed");
+    let config = config(&scratch, None);
+    let path = scratch.write("userdata0000", &save_at(1.0));
+    let other = scratch.write("userdata0001", &save_at(9.0));
+
+    assert!(list_backups(&path, &config).unwrap().is_empty());
+
+    let first = create_backup(&path, &config).unwrap();
+    std::fs::write(&path, save_at(2.0)).unwrap();
+    let second = create_backup(&path, &config).unwrap();
+    create_backup(&other, &config).unwrap();
+
+    assert_eq!(std::fs::read(&first.path).unwrap(), save_at(1.0));
+    assert_eq!(std::fs::read(&second.path).unwrap(), save_at(2.0));
+    assert_eq!(first.size, save().len() as u64);
+    assert!(second.created > first.created);
+
+    // Backups of other save files in the same directory are not listed
+    assert_eq!(list_backups(&path, &config).unwrap(), vec![second, first]);
+}
+
+#[test]
+fn the_oldest_backups_beyond_the_limit_are_pruned() {
+    let scratch = Scratch::new("pruned");
+    let config = config(&scratch, Some(2));
+    let path = scratch.write("userdata0000", &save());
+
+    let created: Vec<_> = (0..4)
+        .map(|_| create_backup(&path, &config).unwrap())
+        .collect();
+    let listed = list_backups(&path, &config).unwrap();
+
+    assert_eq!(listed, vec![created[3].clone(), created[2].clone()]);
+    assert!(!created[0].path.exists());
+    assert!(!created[1].path.exists());
+}
+
+#[test]
+fn restores_back_up_the_current_save_first() {
+    let scratch = Scratch::new("restored");
+    let config = config(&scratch, None);
+    let path = scratch.write("userdata0000", &save_at(1.0));
+    let backup = create_backup(&path, &config).unwrap();
+    std::fs::write(&path, save_at(2.0)).unwrap();
+
+    restore_backup(&path, &backup, &config).unwrap();
+    let listed = list_backups(&path, &config).unwrap();
+
+    assert_eq!(std::fs::read(&path).unwrap(), save_at(1.0));
+    assert_eq!(listed.len(), 2);
+    assert_eq!(std::fs::read(&listed[0].path).unwrap(), save_at(2.0));
+}
+
+#[test]
+fn invalid_backups_are_not_restored() {
+    let scratch = Scratch::new("invalid");
+    let config = config(&scratch, None);
+    let path = scratch.write("userdata0000", &save_at(1.0));
+    let backup = create_backup(&path, &config).unwrap();
+    std::fs::write(&backup.path, b"not a save").unwrap();
+
+    assert!(matches!(
+        restore_backup(&path, &backup, &config),
+        Err(TeleportError::FileTooSmall { .. })
+    ));
+    assert_eq!(std::fs::read(&path).unwrap(), save_at(1.0));
+    assert_eq!(list_backups(&path, &config).unwrap().len(), 1);
+}
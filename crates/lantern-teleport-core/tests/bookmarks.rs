@@ -0,0 +1,50 @@
+//! Bookmarks are checked again before they are used as a destination
+
+#![cfg(feature = "user-files")]
+
+use lantern_teleport_core::{Bookmark, BookmarkStore, TeleportError, teleport_to_bookmark};
+
+mod common;
+use common::{Scratch, save};
+
+fn bookmark(map_id: [u8; 4]) -> Bookmark {
+    Bookmark {
+        name: "Edited by hand".to_string(),
+        x: 1.0,
+        y: 2.0,
+        z: 3.0,
+        map_id,
+    }
+}
+
+#[test]
+fn bookmarks_with_unknown_map_ids_are_refused() {
+    let scratch = Scratch::new("bookmark-map");
+    let path = scratch.write("userdata0000", &save());
+
+    assert!(matches!(
+        teleport_to_bookmark(&path, &bookmark([0x00, 0x00, 0x07, 0x99]), &scratch.options),
+        Err(TeleportError::UnknownMapId(_))
+    ));
+    assert!(matches!(
+        teleport_to_bookmark(&path, &bookmark([0x01, 0x00, 0x00, 0x15]), &scratch.options),
+        Err(TeleportError::InvalidMapId(_))
+    ));
+    assert_eq!(std::fs::read(&path).unwrap(), save());
+
+    teleport_to_bookmark(&path, &bookmark([0x00, 0x00, 0x00, 0x15]), &scratch.options).unwrap();
+}
+
+#[test]
+fn unreadable_bookmark_files_keep_the_parse_error_as_source() {
+    let scratch = Scratch::new("bookmark-source");
+    let path = scratch.write("bookmarks.toml", b"[[bookmarks]\nname = ");
+
+    let error = BookmarkStore::load(&path).unwrap_err();
+    assert!(matches!(
+        &error,
+        TeleportError::BookmarkError { path: Some(p), .. } if *p == path
+    ));
+    let source = std::error::Error::source(&error).expect("the TOML error is kept");
+    assert!(source.downcast_ref::<toml::de::Error>().is_some());
+}
@@ -0,0 +1,172 @@
+//! Loading, validating and installing location catalogs
+#![cfg(feature = "user-files")]
+
+use std::error::Error;
+
+use lantern_teleport_core::{
+    Catalog, LOCATIONS, TeleportError, get_all_locations, search_locations,
+};
+
+mod common;
+use common::Scratch;
+
+const FOG_GATE: &str = r#"
+[[locations]]
+name = "Cleric Beast Fog Gate"
+region = "Yharnam Headstone"
+x = -160.0
+y = -25.0
+z = 60.0
+map_id = [0x01, 0x18]
+facing = 90.0
+"#;
+
+const ORPHANAGE: &str = r#"{
+    "locations": [
+        {"name": "Orphanage Door", "region": "Byrgenwerth", "x": 1.5, "y": 2.5, "z": 3.5, "map_id": [0, 32]}
+    ]
+}"#;
+
+/// A TOML catalog with one Cathedral Ward entry built from the given fields
+fn entry(fields: &str) -> String {
+    format!(
+        "[[locations]]\nregion = \"Cathedral Ward\"\nmap_id = [0, 0x18]\n{}\n",
+        fields
+    )
+}
+
+/// The message of a `CatalogError`
+fn message(result: Result<(), TeleportError>) -> String {
+    match result {
+        Err(TeleportError::CatalogError { message, .. }) => message,
+        other => panic!("expected a catalog error, got {:?}", other),
+    }
+}
+
+#[test]
+fn toml_and_json_files_are_merged_after_the_builtin_lanterns() {
+    let scratch = Scratch::new("merged");
+    scratch.write("b.json", ORPHANAGE.as_bytes());
+    scratch.write("a.toml", FOG_GATE.as_bytes());
+    scratch.write("notes.txt", b"not a catalog");
+
+    let mut catalog = Catalog::builtin();
+    catalog.load_dir(&scratch.dir).unwrap();
+    let added: Vec<_> = catalog.locations()[LOCATIONS.len()..].to_vec();
+
+    assert_eq!(catalog.locations()[..LOCATIONS.len()], LOCATIONS[..]);
+    assert_eq!(added.len(), 2);
+    assert_eq!(added[0].name, "Cleric Beast Fog Gate");
+    assert_eq!(added[0].map_id, [0x01, 0x18]);
+    assert_eq!(added[0].facing, Some(90.0));
+    assert_eq!(added[1].name, "Orphanage Door");
+    assert_eq!((added[1].x, added[1].y, added[1].z), (1.5, 2.5, 3.5));
+
+    // A missing directory is not an error
+    catalog.load_dir(scratch.dir.join("missing")).unwrap();
+    assert_eq!(catalog.locations().len(), LOCATIONS.len() + 2);
+}
+
+#[test]
+fn entries_breaking_the_schema_are_rejected() {
+    let scratch = Scratch::new("schema");
+    let cases = [
+        (
+            "name = \" \"\nx = 0.0\ny = 0.0\nz = 0.0",
+            "name cannot be empty",
+        ),
+        (
+            "name = \"A\"\nx = nan\ny = 0.0\nz = 0.0",
+            "x coordinate must be finite",
+        ),
+        (
+            "name = \"B\"\nx = 0.0\ny = 0.0\nz = -inf",
+            "z coordinate must be finite",
+        ),
+        (
+            "name = \"C\"\nx = 0.0\ny = 0.0\nz = 0.0\nfacing = inf",
+            "facing must be finite",
+        ),
+    ];
+    for (i, (fields, expected)) in cases.iter().enumerate() {
+        let path = scratch.write(&format!("case{}.toml", i), entry(fields).as_bytes());
+        let mut catalog = Catalog::builtin();
+        let found = message(catalog.load_file(&path));
+        assert!(found.ends_with(expected), "{:?} for {:?}", found, fields);
+        assert_eq!(catalog.locations().len(), LOCATIONS.len());
+    }
+
+    let unknown_map = FOG_GATE.replace("[0x01, 0x18]", "[0x99, 0x99]");
+    let path = scratch.write("map.toml", unknown_map.as_bytes());
+    assert!(message(Catalog::builtin().load_file(&path)).ends_with("unknown map ID 99:99"));
+}
+
+#[test]
+fn unparseable_files_keep_the_parse_error_as_source() {
+    let scratch = Scratch::new("unparseable");
+    let unknown_field = FOG_GATE.replace("facing", "heading");
+    let toml = scratch.write("fields.toml", unknown_field.as_bytes());
+    let json = scratch.write("broken.json", b"{\"locations\": [");
+    let text = scratch.write("catalog.txt", FOG_GATE.as_bytes());
+
+    for (path, expected) in [(&toml, "invalid TOML"), (&json, "invalid JSON")] {
+        let error = Catalog::builtin().load_file(path).unwrap_err();
+        assert!(
+            matches!(&error, TeleportError::CatalogError { message, .. } if message == expected)
+        );
+        assert!(error.source().is_some());
+    }
+
+    let missing = Catalog::builtin()
+        .load_file(scratch.dir.join("missing.toml"))
+        .unwrap_err();
+    let source = missing.source().unwrap();
+    assert!(source.downcast_ref::<std::io::Error>().is_some());
+
+    assert_eq!(
+        message(Catalog::builtin().load_file(&text)),
+        "expected a .toml or .json file"
+    );
+}
+
+#[test]
+fn duplicate_names_are_refused() {
+    let scratch = Scratch::new("duplicates");
+    let builtin_name = FOG_GATE.replace(
+        "Cleric Beast Fog Gate",
+        &LOCATIONS[0].name.to_ascii_uppercase(),
+    );
+    let twice = format!("{}{}", FOG_GATE, FOG_GATE.replace("Gate", "GATE"));
+    let cases = [
+        (builtin_name, LOCATIONS[0].name.to_ascii_uppercase()),
+        (twice, "Cleric Beast Fog GATE".to_string()),
+    ];
+
+    for (i, (contents, expected)) in cases.into_iter().enumerate() {
+        let path = scratch.write(&format!("dup{}.toml", i), contents.as_bytes());
+        let mut catalog = Catalog::builtin();
+        match catalog.load_file(&path) {
+            Err(TeleportError::DuplicateLocation(name)) => assert_eq!(name, expected),
+            other => panic!("expected a duplicate, got {:?}", other),
+        }
+        assert_eq!(catalog.locations().len(), LOCATIONS.len());
+    }
+}
+
+#[test]
+fn a_catalog_can_only_be_installed_once() {
+    let scratch = Scratch::new("install");
+    let path = scratch.write("a.toml", FOG_GATE.as_bytes());
+    let mut catalog = Catalog::builtin();
+    catalog.load_file(&path).unwrap();
+
+    catalog.clone().install().unwrap();
+    assert_eq!(get_all_locations().len(), LOCATIONS.len() + 1);
+    assert_eq!(search_locations("cleric beast fog").len(), 1);
+
+    assert_eq!(
+        message(Catalog::builtin().install()),
+        "a catalog is already installed"
+    );
+    assert_eq!(get_all_locations().len(), LOCATIONS.len() + 1);
+}
@@ -0,0 +1,69 @@
+//! Character metadata decoded relative to the LCED marker
+
+use std::time::Duration;
+
+use lantern_teleport_core::{CharacterInfo, SaveFile, read_character_bytes};
+
+mod common;
+use common::{LCED, PATTERN, put_u32, save};
+
+/// Write a UTF-16LE name at `offset`
+fn put_name(bytes: &mut [u8], offset: usize, name: &str) {
+    for (i, unit) in name.encode_utf16().enumerate() {
+        bytes[offset + i * 2..offset + i * 2 + 2].copy_from_slice(&unit.to_le_bytes());
+    }
+}
+
+#[test]
+fn character_fields_are_decoded() {
+    let mut bytes = save();
+    put_u32(&mut bytes, LCED + 0x04, 3_723_000);
+    put_u32(&mut bytes, LCED + 0x08, 120);
+    put_u32(&mut bytes, LCED + 0x0C, 15);
+    put_u32(&mut bytes, LCED + 0x10, 45_000);
+    put_u32(&mut bytes, LCED + 0x14, 2);
+    put_name(&mut bytes, LCED + 0x18, "Gehrman");
+
+    let character = read_character_bytes(&bytes).unwrap();
+    assert_eq!(
+        character,
+        CharacterInfo {
+            name: Some("Gehrman".to_string()),
+            level: Some(120),
+            insight: Some(15),
+            blood_echoes: Some(45_000),
+            playtime: Some(Duration::from_secs(3723)),
+            ng_cycle: Some(2),
+        }
+    );
+    assert_eq!(SaveFile::from_bytes(bytes).unwrap().character(), character);
+}
+
+#[test]
+fn implausible_fields_are_unknown() {
+    let mut bytes = save();
+    put_u32(&mut bytes, LCED + 0x08, 0);
+    put_u32(&mut bytes, LCED + 0x0C, 5000);
+    put_u32(&mut bytes, LCED + 0x14, u32::MAX);
+    put_u32(&mut bytes, LCED + 0x18, 0x0001_D800);
+
+    let character = read_character_bytes(&bytes).unwrap();
+    assert_eq!(character.name, None);
+    assert_eq!(character.level, None);
+    assert_eq!(character.insight, None);
+    assert_eq!(character.ng_cycle, None);
+    assert_eq!(character.blood_echoes, Some(0));
+}
+
+#[test]
+fn fields_past_the_end_are_unknown() {
+    // The coordinate block directly follows the marker and ends the file
+    let mut bytes = vec![0u8; 0x5C];
+    bytes[LCED..LCED + 4].copy_from_slice(b"LCED");
+    bytes[LCED + 4..LCED + 16].copy_from_slice(&PATTERN);
+    put_name(&mut bytes, LCED + 0x18, "Ei");
+
+    let character = read_character_bytes(&bytes).unwrap();
+    assert_eq!(character.name, None);
+    assert_eq!(character.playtime, None);
+}
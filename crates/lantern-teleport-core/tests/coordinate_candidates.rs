@@ -0,0 +1,131 @@
+//! Candidate coordinate blocks and refusal of ambiguous writes
+
+use lantern_teleport_core::{
+    LOCATIONS, Location, TeleportError, nudge, read_journal, scan_coordinate_candidates,
+    teleport_bytes, teleport_with_options, validate_save_bytes, validate_save_file,
+};
+
+mod common;
+use common::{PATTERN, Scratch, put_f32s, save};
+
+/// [`save`] with one coordinate block per entry of `blocks`, each 0x40 bytes
+/// apart starting at the usual block
+fn save_with_blocks(blocks: &[[f32; 3]]) -> Vec<u8> {
+    let mut bytes = save();
+    bytes.resize(0x80 + 0x40 * blocks.len(), 0);
+
+    for (i, block) in blocks.iter().enumerate() {
+        let at = 0x80 + 0x40 * i;
+        bytes[at..at + 12].copy_from_slice(&PATTERN);
+        put_f32s(&mut bytes, at + 12, block);
+    }
+    bytes
+}
+
+#[test]
+fn every_candidate_is_listed_with_its_values() {
+    let bytes = save_with_blocks(&[[-8.0, -6.0, 4.0], [f32::NAN, 1.0, 2.0], [1e30, 0.0, 0.0]]);
+    let candidates = scan_coordinate_candidates(&bytes).unwrap();
+
+    assert_eq!(
+        candidates.iter().map(|c| c.offset).collect::<Vec<_>>(),
+        [0x8C, 0xCC, 0x10C]
+    );
+    assert_eq!([candidates[0].x, candidates[0].y], [-8.0, -6.0]);
+    assert!(candidates[0].is_plausible());
+    assert_eq!(candidates[1].score, 0.0);
+    assert!(!candidates[2].is_plausible());
+}
+
+#[test]
+fn implausible_extra_blocks_do_not_block_writes() {
+    let mut bytes = save_with_blocks(&[[-8.0, -6.0, 4.0], [f32::NAN, 1.0, 2.0], [0.0, 0.0, 0.0]]);
+    teleport_bytes(&mut bytes, &LOCATIONS[1]).unwrap();
+
+    assert_eq!(validate_save_bytes(&bytes).unwrap().x, LOCATIONS[1].x);
+}
+
+#[test]
+fn the_first_block_is_written_even_if_only_a_later_one_is_plausible() {
+    let mut bytes = save_with_blocks(&[[f32::NAN, 1.0, 2.0], [-8.0, -6.0, 4.0]]);
+    assert!(validate_save_bytes(&bytes).unwrap().x.is_nan());
+
+    teleport_bytes(&mut bytes, &LOCATIONS[1]).unwrap();
+
+    let candidates = scan_coordinate_candidates(&bytes).unwrap();
+    assert_eq!(candidates[0].x, LOCATIONS[1].x);
+    assert_eq!(candidates[1].x, -8.0);
+}
+
+#[test]
+fn custom_coordinates_outside_the_catalog_bounds_verify_and_read_back() {
+    let scratch = Scratch::new("custom-far");
+    let path = scratch.write(
+        "userdata0000",
+        &save_with_blocks(&[[f32::NAN, 1.0, 2.0], [-8.0, -6.0, 4.0]]),
+    );
+    let far = Location::custom(5000.0, 5000.0, 5000.0, [0x00, 0x15]).unwrap();
+
+    teleport_with_options(&path, &far, &scratch.options).unwrap();
+
+    let position = validate_save_file(&path).unwrap();
+    assert_eq!([position.x, position.y, position.z], [5000.0; 3]);
+    assert_eq!(
+        read_journal(&path, &scratch.options.backups).unwrap().len(),
+        1
+    );
+}
+
+#[test]
+fn nudges_outside_the_catalog_bounds_verify_and_read_back() {
+    let scratch = Scratch::new("nudge-far");
+    let path = scratch.write(
+        "userdata0000",
+        &save_with_blocks(&[[6000.0, 1.0, 2.0], [-8.0, -6.0, 4.0]]),
+    );
+
+    let (position, _) = nudge(&path, 0.0, 3000.0, 0.0, &scratch.options).unwrap();
+
+    assert_eq!([position.x, position.y], [6000.0, 3001.0]);
+    assert_eq!(validate_save_file(&path).unwrap(), position);
+    assert_eq!(
+        read_journal(&path, &scratch.options.backups).unwrap().len(),
+        1
+    );
+}
+
+#[test]
+fn several_plausible_blocks_refuse_writes() {
+    let original = save_with_blocks(&[[-8.0, -6.0, 4.0], [10.0, 20.0, 30.0]]);
+    let mut bytes = original.clone();
+
+    match teleport_bytes(&mut bytes, &LOCATIONS[1]) {
+        Err(TeleportError::MultipleCoordinateBlocks { offsets }) => {
+            assert_eq!(offsets, [0x8C, 0xCC])
+        }
+        other => panic!("expected MultipleCoordinateBlocks, got {:?}", other),
+    }
+    assert_eq!(bytes, original);
+}
+
+#[test]
+fn allow_ambiguous_writes_to_the_first_block() {
+    let scratch = Scratch::new("ambiguous");
+    let path = scratch.write(
+        "userdata0000",
+        &save_with_blocks(&[[-8.0, -6.0, 4.0], [10.0, 20.0, 30.0]]),
+    );
+    let mut options = scratch.options.clone();
+    assert!(matches!(
+        teleport_with_options(&path, &LOCATIONS[1], &options),
+        Err(TeleportError::MultipleCoordinateBlocks { .. })
+    ));
+
+    options.allow_ambiguous = true;
+    teleport_with_options(&path, &LOCATIONS[1], &options).unwrap();
+
+    let bytes = std::fs::read(&path).unwrap();
+    let candidates = scan_coordinate_candidates(&bytes).unwrap();
+    assert_eq!(candidates[0].x, LOCATIONS[1].x);
+    assert_eq!(candidates[1].x, 10.0);
+}
@@ -0,0 +1,85 @@
+//! Copying the position of one save file into another
+
+use std::path::PathBuf;
+
+use lantern_teleport_core::{SaveFile, TeleportWarning, copy_position, read_journal};
+
+mod common;
+use common::{Scratch, save};
+
+/// A scratch directory with the source save as `userdata0000` and a fresh
+/// [`save`] as the target `userdata0003`
+fn setup(name: &str, source: &[u8]) -> (Scratch, PathBuf, PathBuf) {
+    let scratch = Scratch::new(name);
+    let source_path = scratch.write("userdata0000", source);
+    let target_path = scratch.write("userdata0003", &save());
+    (scratch, source_path, target_path)
+}
+
+#[test]
+fn position_and_map_are_copied() {
+    let mut source = SaveFile::from_bytes(save()).unwrap();
+    source.set_map_id([0x00, 0x00, 0x01, 0x18]).unwrap();
+    source.set_position(-160.5, -25.0, 60.25).unwrap();
+    source.set_facing(90.0).unwrap();
+    let source_bytes = source.into_bytes();
+    let (scratch, source_path, target_path) = setup("copied", &source_bytes);
+    let options = &scratch.options;
+    let facing_before = SaveFile::load(&target_path).unwrap().facing();
+
+    let (position, _) = copy_position(&source_path, &target_path, options).unwrap();
+    let target = SaveFile::load(&target_path).unwrap();
+    let entries = read_journal(&target_path, &options.backups).unwrap();
+    let source_after = std::fs::read(&source_path).unwrap();
+
+    assert_eq!(target.position().unwrap(), position);
+    assert_eq!(position.map_id, [0x00, 0x00, 0x01, 0x18]);
+    assert_eq!(position.x, -160.5);
+    assert_eq!(target.facing(), facing_before);
+    assert_eq!(source_after, source_bytes);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].to, position);
+}
+
+#[test]
+fn the_facing_is_only_copied_when_asked_for() {
+    let mut source = SaveFile::from_bytes(save()).unwrap();
+    source.set_facing(90.0).unwrap();
+    let (scratch, source_path, target_path) = setup("facing", &source.into_bytes());
+    let mut options = scratch.options.clone();
+    options.write_facing = true;
+
+    copy_position(&source_path, &target_path, &options).unwrap();
+    let target = SaveFile::load(&target_path).unwrap();
+
+    assert!((target.facing().unwrap() - 90.0).abs() < 0.01);
+}
+
+#[test]
+fn dlc_positions_warn_without_dlc_progress() {
+    let mut source = SaveFile::from_bytes(save()).unwrap();
+    source.set_map_id([0x00, 0x00, 0x00, 0x22]).unwrap();
+    let (scratch, source_path, target_path) = setup("dlc", &source.into_bytes());
+    let mut options = scratch.options.clone();
+
+    let (position, warned) = copy_position(&source_path, &target_path, &options).unwrap();
+    options.force_dlc = true;
+    let (_, forced) = copy_position(&source_path, &target_path, &options).unwrap();
+
+    assert_eq!(position.map_id, [0x00, 0x00, 0x00, 0x22]);
+    assert!(matches!(
+        warned.warnings[..],
+        [TeleportWarning::NoDlcProgress { .. }]
+    ));
+    assert!(forced.warnings.is_empty());
+}
+
+#[test]
+fn invalid_sources_leave_the_target_alone() {
+    let (scratch, source_path, target_path) = setup("invalid", &[0u8; 0x40]);
+    let result = copy_position(&source_path, &target_path, &scratch.options);
+    let unchanged = std::fs::read(&target_path).unwrap();
+
+    assert!(result.is_err());
+    assert_eq!(unchanged, save());
+}
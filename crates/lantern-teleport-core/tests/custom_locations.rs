@@ -0,0 +1,59 @@
+//! Parsing and validating custom destinations (`--coords` and `--map`)
+
+use lantern_teleport_core::{Location, MapId, TeleportError, parse_coordinates};
+
+#[test]
+fn coordinate_triples_are_parsed() {
+    assert_eq!(parse_coordinates("1,-2.5,3e2").unwrap(), [1.0, -2.5, 300.0]);
+    assert_eq!(
+        parse_coordinates(" -160.5 , -25 ,60.25 ").unwrap(),
+        [-160.5, -25.0, 60.25]
+    );
+}
+
+#[test]
+fn malformed_triples_are_rejected() {
+    for text in ["", "1,2", "1,2,3,4", "1;2;3", "1,,3", "x,2,3", "1,2,3,"] {
+        assert!(
+            matches!(
+                parse_coordinates(text),
+                Err(TeleportError::InvalidCoordinates(ref t)) if t == text
+            ),
+            "{:?} was accepted",
+            text
+        );
+    }
+}
+
+#[test]
+fn non_finite_coordinates_are_rejected() {
+    for (text, expected_axis) in [("NaN,0,0", 'X'), ("0,inf,0", 'Y'), ("0,0,-inf", 'Z')] {
+        match parse_coordinates(text) {
+            Err(TeleportError::NonFiniteCoordinate { axis, .. }) => assert_eq!(axis, expected_axis),
+            other => panic!("{:?} gave {:?}", text, other),
+        }
+    }
+
+    assert!(matches!(
+        Location::custom(0.0, f32::NAN, 0.0, [0x00, 0x15]),
+        Err(TeleportError::NonFiniteCoordinate { axis: 'Y', .. })
+    ));
+    assert!(matches!(
+        Location::custom(0.0, 0.0, f32::INFINITY, [0x00, 0x15]),
+        Err(TeleportError::NonFiniteCoordinate { axis: 'Z', .. })
+    ));
+}
+
+#[test]
+fn custom_locations_need_a_known_map_id() {
+    assert!(matches!(
+        Location::custom(1.0, 2.0, 3.0, [0x99, 0x99]),
+        Err(TeleportError::UnknownMapId([0x99, 0x99]))
+    ));
+
+    let map: MapId = "01:18".parse().unwrap();
+    let location = Location::custom(1.0, 2.0, 3.0, map.to_bytes()).unwrap();
+    assert_eq!(location.map_id, [0x01, 0x18]);
+    assert_eq!((location.x, location.y, location.z), (1.0, 2.0, 3.0));
+    assert_eq!(location.facing, None);
+}
@@ -0,0 +1,87 @@
+//! Teleports into The Old Hunters maps warn about saves without DLC progress
+
+use lantern_teleport_core::{SaveFile, TeleportWarning, teleport_bytes, teleport_with_options};
+
+mod common;
+use common::{LCED, PATTERN, Scratch, location, save};
+
+/// Start of the event flag block in [`save`]
+const FLAGS: usize = LCED + 0x38;
+
+#[test]
+fn dlc_destinations_warn_without_dlc_progress() {
+    let save = SaveFile::from_bytes(save()).unwrap();
+    assert!(!save.has_dlc_progress().unwrap());
+    assert!(matches!(
+        save.dlc_warning(&location("Fishing Hamlet").map_id),
+        Some(TeleportWarning::NoDlcProgress { .. })
+    ));
+
+    // Base game destinations are unaffected
+    assert_eq!(save.dlc_warning(&location("Byrgenwerth").map_id), None);
+}
+
+#[test]
+fn teleports_without_dlc_progress_are_written_with_a_warning() {
+    let scratch = Scratch::new("dlc-warning");
+    let path = scratch.write("userdata0000", &save());
+
+    let outcome =
+        teleport_with_options(&path, location("Lighthouse Hut"), &scratch.options).unwrap();
+
+    assert!(
+        outcome
+            .warnings
+            .iter()
+            .any(|warning| matches!(warning, TeleportWarning::NoDlcProgress { .. }))
+    );
+    let position = SaveFile::load(&path).unwrap().position().unwrap();
+    assert_eq!(position.map_id, [0x00, 0x00, 0x00, 0x24]);
+}
+
+#[test]
+fn dlc_entry_flag_or_a_dlc_lantern_counts_as_progress() {
+    // Flag 39: the DLC entry flag
+    let mut bytes = save();
+    bytes[FLAGS + 4] = 0b0000_0001;
+    let save_file = SaveFile::from_bytes(bytes).unwrap();
+    assert!(save_file.has_dlc_progress().unwrap());
+    assert_eq!(save_file.dlc_warning(&location("Coast").map_id), None);
+
+    // Flag 40: Hunter's Nightmare lantern
+    let mut bytes = save();
+    bytes[FLAGS + 5] = 0b1000_0000;
+    let save_file = SaveFile::from_bytes(bytes).unwrap();
+    assert_eq!(
+        save_file.dlc_warning(&location("Research Hall").map_id),
+        None
+    );
+}
+
+#[test]
+fn unreadable_dlc_progress_warns() {
+    // The coordinate block directly follows the marker and ends the file
+    let mut bytes = vec![0u8; 0x5C];
+    bytes[LCED..LCED + 4].copy_from_slice(b"LCED");
+    bytes[LCED + 4..LCED + 16].copy_from_slice(&PATTERN);
+
+    teleport_bytes(&mut bytes, location("Coast")).unwrap();
+
+    let save = SaveFile::from_bytes(bytes).unwrap();
+    assert!(save.has_dlc_progress().is_err());
+    assert!(matches!(
+        save.destination_warnings(&location("Coast").map_id)[..],
+        [TeleportWarning::DlcProgressUnknown { .. }]
+    ));
+    assert_eq!(save.dlc_warning(&location("Byrgenwerth").map_id), None);
+}
+
+#[test]
+fn forcing_silences_the_warning() {
+    let mut save = SaveFile::from_bytes(save()).unwrap();
+    save.set_force_dlc(true);
+    assert_eq!(save.dlc_warning(&location("Coast").map_id), None);
+
+    save.teleport_to(location("Lighthouse Hut")).unwrap();
+    assert_eq!(save.position().unwrap().map_id, [0x00, 0x00, 0x00, 0x24]);
+}
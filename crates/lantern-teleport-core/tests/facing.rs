@@ -0,0 +1,98 @@
+//! Character facing stored as a yaw right after the coordinate block
+
+use lantern_teleport_core::{
+    LOCATIONS, Location, SaveFile, TeleportError, teleport_bytes, teleport_with_options,
+};
+
+mod common;
+use common::{COORDS, LCED, PATTERN, Scratch, get_f32, put_f32s, save};
+
+/// Offset of the yaw in [`save`]
+const YAW: usize = COORDS + 12;
+
+#[test]
+fn built_in_lanterns_keep_the_current_facing() {
+    assert!(LOCATIONS.iter().all(|location| location.facing.is_none()));
+
+    let mut bytes = save();
+    put_f32s(&mut bytes, YAW, &[1.0]);
+    teleport_bytes(&mut bytes, &LOCATIONS[1]).unwrap();
+    assert_eq!(get_f32(&bytes, YAW), 1.0);
+}
+
+#[test]
+fn facing_is_stored_as_radians() {
+    let mut save = SaveFile::from_bytes(save()).unwrap();
+    save.set_facing(270.0).unwrap();
+    assert!((get_f32(save.bytes(), YAW) + std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+    assert!((save.facing().unwrap() - 270.0).abs() < 1e-3);
+
+    assert!(matches!(
+        save.set_facing(f32::NAN),
+        Err(TeleportError::NonFiniteFacing(_))
+    ));
+}
+
+#[test]
+fn the_destination_facing_is_only_written_when_asked_for() {
+    let mut bytes = save();
+    put_f32s(&mut bytes, YAW, &[1.0]);
+    let turned = Location::custom(1.0, 2.0, 3.0, [0x00, 0x15])
+        .unwrap()
+        .with_facing(-90.0)
+        .unwrap();
+
+    teleport_bytes(&mut bytes, &turned).unwrap();
+    assert_eq!(get_f32(&bytes, YAW), 1.0);
+
+    let mut save = SaveFile::from_bytes(bytes).unwrap();
+    save.teleport_to(&turned).unwrap();
+    assert_eq!(get_f32(save.bytes(), YAW), 1.0);
+
+    save.set_write_facing(true);
+    save.teleport_to(&turned).unwrap();
+    assert!((save.facing().unwrap() - 270.0).abs() < 1e-3);
+}
+
+#[test]
+fn teleports_keep_the_facing_unless_the_options_ask_for_it() {
+    let scratch = Scratch::new("facing-option");
+    let mut bytes = save();
+    put_f32s(&mut bytes, YAW, &[1.0]);
+    let path = scratch.write("userdata0000", &bytes);
+    let turned = LOCATIONS[1].clone().with_facing(90.0).unwrap();
+    let mut options = scratch.options.clone();
+
+    teleport_with_options(&path, &turned, &options).unwrap();
+    assert_eq!(get_f32(&std::fs::read(&path).unwrap(), YAW), 1.0);
+
+    options.write_facing = true;
+    teleport_with_options(&path, &turned, &options).unwrap();
+    let facing = SaveFile::load(&path).unwrap().facing().unwrap();
+    assert!((facing - 90.0).abs() < 1e-3);
+}
+
+#[test]
+fn saves_ending_after_the_coordinates_keep_no_facing() {
+    let mut bytes = vec![0u8; 0x5C];
+    bytes[LCED..LCED + 4].copy_from_slice(b"LCED");
+    bytes[LCED + 4..LCED + 16].copy_from_slice(&PATTERN);
+
+    teleport_bytes(&mut bytes, &LOCATIONS[0]).unwrap();
+
+    // An explicit facing that does not fit fails before anything is written
+    let mut save = SaveFile::from_bytes(bytes.clone()).unwrap();
+    save.set_write_facing(true);
+    let facing = LOCATIONS[1].clone().with_facing(90.0).unwrap();
+    assert!(matches!(
+        save.teleport_to(&facing),
+        Err(TeleportError::InvalidOffset { .. })
+    ));
+    assert_eq!(save.bytes(), &bytes[..]);
+
+    assert_eq!(save.facing(), None);
+    assert!(matches!(
+        save.set_facing(0.0),
+        Err(TeleportError::InvalidOffset { .. })
+    ));
+}
@@ -0,0 +1,82 @@
+//! Identifying the area and nearest catalog location of a position
+
+use lantern_teleport_core::{CurrentPosition, LOCATIONS, Location, MapId, identify_position};
+
+/// A position `offset` away from `location`, on `map`
+fn near(location: &Location, map: MapId, [dx, dy, dz]: [f32; 3]) -> CurrentPosition {
+    CurrentPosition {
+        x: location.x + dx,
+        y: location.y + dy,
+        z: location.z + dz,
+        map_id: map.to_save_format(),
+    }
+}
+
+fn map_of(location: &Location) -> MapId {
+    MapId::from_bytes(location.map_id).unwrap()
+}
+
+#[test]
+fn positions_at_a_lantern_are_identified_exactly() {
+    for location in LOCATIONS.iter() {
+        let info = identify_position(&near(location, map_of(location), [0.0; 3])).unwrap();
+        let nearest = info.nearest.unwrap();
+
+        assert_eq!(info.map, map_of(location));
+        assert_eq!(nearest.distance, 0.0);
+        assert_eq!(
+            (nearest.location.x, nearest.location.y, nearest.location.z),
+            (location.x, location.y, location.z)
+        );
+    }
+}
+
+#[test]
+fn distances_are_straight_line_distances() {
+    let location = &LOCATIONS[0];
+    let info = identify_position(&near(location, map_of(location), [0.3, 0.4, 0.0])).unwrap();
+    let nearest = info.nearest.unwrap();
+
+    assert_eq!(nearest.location.name, location.name);
+    assert!(
+        (nearest.distance - 0.5).abs() < 1e-3,
+        "{}",
+        nearest.distance
+    );
+}
+
+#[test]
+fn only_locations_on_the_same_map_are_considered() {
+    for map in MapId::all() {
+        let on_map: Vec<&Location> = LOCATIONS
+            .iter()
+            .filter(|loc| loc.map_id == map.to_bytes())
+            .collect();
+        // Standing right at a lantern of another map
+        let elsewhere = LOCATIONS
+            .iter()
+            .find(|loc| loc.map_id != map.to_bytes())
+            .unwrap();
+        let info = identify_position(&near(elsewhere, map, [0.0; 3])).unwrap();
+
+        assert_eq!(info.map, map);
+        match info.nearest {
+            Some(nearest) => {
+                assert_eq!(nearest.location.map_id, map.to_bytes());
+                assert!(on_map.iter().any(|loc| loc.name == nearest.location.name));
+            }
+            None => assert!(on_map.is_empty(), "{} has lanterns", map),
+        }
+    }
+}
+
+#[test]
+fn unknown_maps_are_not_identified() {
+    let position = CurrentPosition {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        map_id: [0x00, 0x00, 0x99, 0x99],
+    };
+    assert_eq!(identify_position(&position), None);
+}
@@ -0,0 +1,103 @@
+//! Teleport journal and undo
+
+use std::path::PathBuf;
+
+use lantern_teleport_core::{
+    JournalAction, LOCATIONS, SaveFile, TeleportError, read_journal, teleport_with_options,
+    undo_last_teleport,
+};
+
+mod common;
+use common::{COORDS, Scratch, put_f32s, save};
+
+/// A scratch directory holding a save whose X coordinate is not zero, so a
+/// restored position is told apart from a zeroed one
+fn setup(name: &str) -> (Scratch, PathBuf) {
+    let mut bytes = save();
+    put_f32s(&mut bytes, COORDS, &[1.5]);
+    let scratch = Scratch::new(name);
+    let path = scratch.write("userdata0000", &bytes);
+    (scratch, path)
+}
+
+#[test]
+fn teleports_are_journaled_and_undone_in_order() {
+    let (scratch, path) = setup("order");
+    let options = scratch.options.clone();
+    let original = SaveFile::load(&path).unwrap().position().unwrap();
+
+    teleport_with_options(&path, &LOCATIONS[1], &options).unwrap();
+    teleport_with_options(&path, &LOCATIONS[2], &options).unwrap();
+    let entries = read_journal(&path, &options.backups).unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].target, LOCATIONS[1].name);
+    assert_eq!(entries[0].from, original);
+    assert_eq!(entries[1].from, entries[0].to);
+
+    let (undone, _) = undo_last_teleport(&path, &options).unwrap();
+    assert_eq!(undone.target, LOCATIONS[2].name);
+    let (undone, _) = undo_last_teleport(&path, &options).unwrap();
+    assert_eq!(undone.target, LOCATIONS[1].name);
+
+    let restored = SaveFile::load(&path).unwrap().position().unwrap();
+    let result = undo_last_teleport(&path, &options);
+    let entries = read_journal(&path, &options.backups).unwrap();
+
+    assert_eq!(restored.x.to_bits(), original.x.to_bits());
+    assert_eq!(restored.map_id, original.map_id);
+    assert!(matches!(result, Err(TeleportError::NothingToUndo)));
+    assert_eq!(entries.len(), 4);
+    assert_eq!(entries[3].action, JournalAction::Undo);
+}
+
+#[test]
+fn undo_restores_every_overwritten_byte() {
+    let (scratch, path) = setup("bytes");
+    let mut options = scratch.options.clone();
+    options.write_facing = true;
+    let mut original = std::fs::read(&path).unwrap();
+    // A facing the teleport overwrites along with the position
+    put_f32s(&mut original, COORDS + 12, &[0.5]);
+    std::fs::write(&path, &original).unwrap();
+
+    let destination = LOCATIONS[1].clone().with_facing(90.0).unwrap();
+    teleport_with_options(&path, &destination, &options).unwrap();
+    let entries = read_journal(&path, &options.backups).unwrap();
+    let overwritten = &entries[0].overwritten;
+    assert!(
+        overwritten
+            .iter()
+            .all(|(offset, bytes)| { original[*offset..*offset + bytes.len()] == bytes[..] })
+    );
+
+    undo_last_teleport(&path, &options).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), original);
+}
+
+#[test]
+fn undo_is_refused_if_the_file_changed() {
+    let (scratch, path) = setup("changed");
+    let options = scratch.options.clone();
+    teleport_with_options(&path, &LOCATIONS[1], &options).unwrap();
+
+    let mut bytes = std::fs::read(&path).unwrap();
+    bytes[0xF0] = 0x42;
+    std::fs::write(&path, &bytes).unwrap();
+
+    let result = undo_last_teleport(&path, &options);
+    let after = std::fs::read(&path).unwrap();
+
+    assert!(matches!(result, Err(TeleportError::JournalMismatch(_))));
+    assert_eq!(after, bytes);
+}
+
+#[test]
+fn saves_without_a_journal_have_no_history() {
+    let (scratch, path) = setup("empty");
+    let options = scratch.options.clone();
+    let entries = read_journal(&path, &options.backups);
+    let result = undo_last_teleport(&path, &options);
+
+    assert!(entries.unwrap().is_empty());
+    assert!(matches!(result, Err(TeleportError::NothingToUndo)));
+}
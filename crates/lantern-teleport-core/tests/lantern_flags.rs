@@ -0,0 +1,95 @@
+//! Lantern unlock flags read relative to the LCED marker
+
+#![cfg(feature = "experimental")]
+
+use lantern_teleport_core::{
+    LOCATIONS, Location, SaveFile, TeleportError, TeleportWarning, lantern_flag,
+    lantern_report_bytes, teleport_with_options,
+};
+
+mod common;
+use common::{LCED, PATTERN, Scratch, location, save};
+
+/// Start of the lantern flag block in [`save`]
+const FLAGS: usize = LCED + 0x38;
+
+#[test]
+fn every_built_in_lantern_has_a_distinct_flag() {
+    let mut flags: Vec<u32> = LOCATIONS
+        .iter()
+        .map(|loc| lantern_flag(loc).unwrap())
+        .collect();
+    flags.sort();
+    flags.dedup();
+    assert_eq!(flags.len(), LOCATIONS.len());
+}
+
+#[test]
+fn lit_lanterns_are_reported() {
+    let mut bytes = save();
+    // Flags 0 (Hunter's Dream) and 2 (Central Yharnam), most significant bit first
+    bytes[FLAGS] = 0b1010_0000;
+
+    let statuses = lantern_report_bytes(&bytes).unwrap();
+    assert_eq!(statuses.len(), LOCATIONS.len());
+    let lit: Vec<&str> = statuses
+        .iter()
+        .filter(|status| status.unlocked == Some(true))
+        .map(|status| status.location.name)
+        .collect();
+    assert_eq!(lit, ["Hunter's Dream", "Central Yharnam"]);
+    assert!(
+        statuses
+            .iter()
+            .filter(|s| !lit.contains(&s.location.name))
+            .all(|s| s.is_locked())
+    );
+
+    let save = SaveFile::from_bytes(bytes).unwrap();
+    assert_eq!(
+        save.is_lantern_unlocked(location("Central Yharnam"))
+            .unwrap(),
+        Some(true)
+    );
+    assert_eq!(
+        save.is_lantern_unlocked(location("Coast")).unwrap(),
+        Some(false)
+    );
+}
+
+#[test]
+fn custom_locations_have_no_flag() {
+    let custom = Location::custom(1.0, 2.0, 3.0, [0x00, 0x15]).unwrap();
+    let save = SaveFile::from_bytes(save()).unwrap();
+
+    assert_eq!(lantern_flag(&custom), None);
+    assert_eq!(save.is_lantern_unlocked(&custom).unwrap(), None);
+}
+
+#[test]
+fn flags_past_the_end_are_an_invalid_offset() {
+    // The coordinate block directly follows the marker and ends the file
+    let mut bytes = vec![0u8; 0x5C];
+    bytes[LCED..LCED + 4].copy_from_slice(b"LCED");
+    bytes[LCED + 4..LCED + 16].copy_from_slice(&PATTERN);
+
+    assert!(matches!(
+        lantern_report_bytes(&bytes),
+        Err(TeleportError::InvalidOffset { .. })
+    ));
+}
+
+#[test]
+fn teleports_to_unlit_lanterns_are_written_with_a_warning() {
+    let scratch = Scratch::new("unlit-lantern");
+    let path = scratch.write("userdata0000", &save());
+
+    let outcome = teleport_with_options(&path, location("Byrgenwerth"), &scratch.options).unwrap();
+
+    assert!(matches!(
+        outcome.warnings[..],
+        [TeleportWarning::LanternNotLit {
+            location: "Byrgenwerth"
+        }]
+    ));
+}
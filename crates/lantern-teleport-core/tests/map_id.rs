@@ -0,0 +1,66 @@
+//! Typed map IDs and the map data derived from them
+
+use lantern_teleport_core::{MapArea, MapId, TeleportError, WorldState};
+
+#[test]
+fn only_story_variants_need_a_world_state() {
+    let required = |bytes: [u8; 2]| MapId::from_bytes(bytes).unwrap().required_world_state();
+    assert_eq!(required([0x00, 0x18]), WorldState::Evening);
+    assert_eq!(required([0x01, 0x18]), WorldState::Evening);
+    // Upper Cathedral Ward is a separate part of Yharnam, not a later state
+    assert_eq!(required([0x02, 0x18]), WorldState::Evening);
+    assert_eq!(required([0x00, 0x20]), WorldState::Evening);
+    assert_eq!(required([0x02, 0x20]), WorldState::BloodMoon);
+}
+
+#[test]
+fn hex_notation_is_parsed() {
+    let parse = |text: &str| text.parse::<MapId>().unwrap().to_bytes();
+    assert_eq!(parse("01:18"), [0x01, 0x18]);
+    assert_eq!(parse("00:1b"), [0x00, 0x1B]);
+    assert_eq!(parse(" 2 : 20 "), [0x02, 0x20]);
+
+    for map in MapId::all() {
+        assert_eq!(map.to_hex().parse::<MapId>().unwrap(), map);
+        assert_eq!(MapId::try_from(map.to_bytes()).unwrap(), map);
+        assert_eq!(MapId::from_save_format(map.to_save_format()).unwrap(), map);
+    }
+}
+
+#[test]
+fn malformed_and_unknown_ids_are_rejected() {
+    for text in ["", "0118", "01-18", "zz:18", "100:18", "01:18:00", ":18"] {
+        assert!(
+            matches!(text.parse::<MapId>(), Err(TeleportError::InvalidMapId(ref t)) if t == text),
+            "{:?} was accepted",
+            text
+        );
+    }
+
+    assert!(matches!(
+        "05:18".parse::<MapId>(),
+        Err(TeleportError::UnknownMapId([0x05, 0x18]))
+    ));
+    assert!(matches!(
+        MapId::from_save_format([0x00, 0x01, 0x01, 0x18]),
+        Err(TeleportError::InvalidMapId(_))
+    ));
+}
+
+#[test]
+fn variants_are_named_after_their_area_unless_named_apart() {
+    let name = |text: &str| text.parse::<MapId>().unwrap().to_string();
+    assert_eq!(name("01:18"), "Central Yharnam");
+    assert_eq!(name("00:18"), "Cathedral Ward");
+    assert_eq!(name("02:18"), "Upper Cathedral Ward");
+    assert_eq!(name("02:20"), "Moonside Lake");
+    assert_eq!(name("01:15"), "Abandoned Old Workshop");
+    assert_eq!(name("00:15"), MapArea::HuntersDream.name());
+    assert_eq!(name("00:20"), MapArea::Byrgenwerth.name());
+
+    let mut names: Vec<&str> = MapId::all().map(MapId::name).collect();
+    let count = names.len();
+    names.sort();
+    names.dedup();
+    assert_eq!(names.len(), count);
+}
@@ -0,0 +1,62 @@
+//! Nudging the character by a small offset
+
+use lantern_teleport_core::{SaveFile, TeleportError, nudge, undo_last_teleport};
+
+mod common;
+use common::{COORDS, Scratch, put_f32s, save};
+
+/// [`save`] moved to Cathedral Ward at (10, -5, 2.5)
+fn stuck() -> Vec<u8> {
+    let mut bytes = save();
+    bytes[0x04..0x08].copy_from_slice(&[0x00, 0x00, 0x00, 0x18]);
+    put_f32s(&mut bytes, COORDS, &[10.0, -5.0, 2.5]);
+    bytes
+}
+
+#[test]
+fn nudge_offsets_the_position_and_keeps_the_map() {
+    let mut save = SaveFile::from_bytes(stuck()).unwrap();
+    let position = save.nudge(1.5, 2.0, -0.5).unwrap();
+
+    assert_eq!((position.x, position.y, position.z), (11.5, -3.0, 2.0));
+    assert_eq!(position.map_id, [0x00, 0x00, 0x00, 0x18]);
+    assert_eq!(save.position().unwrap(), position);
+    assert!(save.is_dirty());
+}
+
+#[test]
+fn non_finite_nudges_are_rejected() {
+    let mut save = SaveFile::from_bytes(stuck()).unwrap();
+    assert!(matches!(
+        save.nudge(f32::NAN, 0.0, 0.0),
+        Err(TeleportError::NonFiniteCoordinate { axis: 'X', .. })
+    ));
+    assert!(matches!(
+        save.nudge(0.0, f32::INFINITY, 0.0),
+        Err(TeleportError::NonFiniteCoordinate { axis: 'Y', .. })
+    ));
+
+    // A nudge that overflows is refused and leaves the position alone
+    save.nudge(0.0, 0.0, f32::MAX).unwrap();
+    assert!(matches!(
+        save.nudge(0.0, 0.0, f32::MAX),
+        Err(TeleportError::NonFiniteCoordinate { axis: 'Z', .. })
+    ));
+    assert_eq!(save.position().unwrap().z, f32::MAX);
+}
+
+#[test]
+fn nudges_are_journaled_and_can_be_undone() {
+    let scratch = Scratch::new("nudge");
+    let path = scratch.write("userdata0000", &stuck());
+    let options = &scratch.options;
+
+    let (nudged, _) = nudge(&path, 0.0, 3.0, 0.0, options).unwrap();
+    let written = SaveFile::load(&path).unwrap().position().unwrap();
+    undo_last_teleport(&path, options).unwrap();
+    let restored = std::fs::read(&path).unwrap();
+
+    assert_eq!(nudged.y, -2.0);
+    assert_eq!(written, nudged);
+    assert_eq!(restored, stuck());
+}
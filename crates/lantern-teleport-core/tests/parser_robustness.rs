@@ -0,0 +1,131 @@
+//! Arbitrary and truncated input must produce errors, never panics
+
+use std::io::Cursor;
+
+use lantern_teleport_core::{
+    LOCATIONS, SaveFile, TeleportError, read_position, teleport_bytes, teleport_stream,
+    validate_save_bytes,
+};
+use proptest::prelude::*;
+
+mod common;
+use common::PATTERN;
+
+const LCED: [u8; 4] = *b"LCED";
+
+/// Filler bytes that can never form the LCED marker or the coordinate pattern
+fn filler(len: std::ops::Range<usize>) -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(0x00u8..0x40, len)
+}
+
+/// Build a well-formed save: header with map ID, filler, LCED, filler,
+/// coordinate pattern, coordinates, trailing filler
+fn build_save(
+    map_id: [u8; 4],
+    gap_before: &[u8],
+    gap_after: &[u8],
+    coords: [f32; 3],
+    tail: &[u8],
+) -> Vec<u8> {
+    let mut bytes = vec![0u8; 8];
+    bytes[4..8].copy_from_slice(&map_id);
+    bytes.extend_from_slice(gap_before);
+    bytes.extend_from_slice(&LCED);
+    bytes.extend_from_slice(gap_after);
+    bytes.extend_from_slice(&PATTERN);
+    for value in coords {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    bytes.extend_from_slice(tail);
+    bytes
+}
+
+prop_compose! {
+    fn valid_save()(
+        map_id in any::<[u8; 4]>(),
+        gap_before in filler(0..64),
+        gap_after in filler(0..64),
+        coords in any::<[f32; 3]>(),
+        tail in filler(0..64),
+    ) -> (Vec<u8>, [f32; 3], usize) {
+        let bytes = build_save(map_id, &gap_before, &gap_after, coords, &tail);
+        let coord_end = bytes.len() - tail.len();
+        (bytes, coords, coord_end)
+    }
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_bytes_never_panic(bytes in prop::collection::vec(any::<u8>(), 0..512)) {
+        let _ = validate_save_bytes(&bytes);
+        let _ = read_position(bytes.as_slice());
+        let _ = teleport_stream(Cursor::new(bytes.clone()), &LOCATIONS[0]);
+
+        if let Ok(mut save) = SaveFile::from_bytes(bytes.clone()) {
+            prop_assert!(save.position().is_ok());
+            prop_assert!(save.teleport_to(&LOCATIONS[0]).is_ok());
+        }
+    }
+
+    #[test]
+    fn failed_teleport_leaves_bytes_untouched(bytes in prop::collection::vec(any::<u8>(), 0..512)) {
+        let mut patched = bytes.clone();
+        if teleport_bytes(&mut patched, &LOCATIONS[0]).is_err() {
+            prop_assert_eq!(patched, bytes);
+        }
+    }
+
+    #[test]
+    fn valid_saves_are_parsed((bytes, coords, _) in valid_save()) {
+        let position = validate_save_bytes(&bytes).unwrap();
+        prop_assert_eq!(position.x.to_bits(), coords[0].to_bits());
+        prop_assert_eq!(position.y.to_bits(), coords[1].to_bits());
+        prop_assert_eq!(position.z.to_bits(), coords[2].to_bits());
+    }
+
+    #[test]
+    fn truncated_saves_are_errors(
+        (bytes, _, coord_end) in valid_save(),
+        cut in any::<prop::sample::Index>(),
+    ) {
+        let truncated = &bytes[..cut.index(coord_end)];
+
+        let result = validate_save_bytes(truncated);
+        prop_assert!(result.is_err());
+        let mut patched = truncated.to_vec();
+        prop_assert!(teleport_bytes(&mut patched, &LOCATIONS[0]).is_err());
+    }
+
+    #[test]
+    fn teleport_round_trips((mut bytes, _, _) in valid_save(), index in 0..LOCATIONS.len()) {
+        let location = &LOCATIONS[index];
+        teleport_bytes(&mut bytes, location).unwrap();
+
+        let position = validate_save_bytes(&bytes).unwrap();
+        prop_assert_eq!(position.x, location.x);
+        prop_assert_eq!(position.y, location.y);
+        prop_assert_eq!(position.z, location.z);
+        prop_assert_eq!(&position.map_id[2..], &location.map_id[..]);
+    }
+}
+
+#[test]
+fn tiny_files_are_too_small() {
+    for len in 0..28 {
+        assert!(matches!(
+            validate_save_bytes(&vec![0u8; len]),
+            Err(TeleportError::FileTooSmall { .. })
+        ));
+    }
+}
+
+#[test]
+fn coordinate_block_past_the_end_is_an_invalid_offset() {
+    let mut bytes = build_save([0; 4], &[0; 32], &[], [1.0, 2.0, 3.0], &[]);
+    bytes.truncate(bytes.len() - 4);
+
+    assert!(matches!(
+        validate_save_bytes(&bytes),
+        Err(TeleportError::InvalidOffset { .. })
+    ));
+}
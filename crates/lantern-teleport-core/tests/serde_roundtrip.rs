@@ -0,0 +1,101 @@
+//! Serialization round-trips for the owned data model
+
+#![cfg(feature = "serde")]
+
+use lantern_teleport_core::{CurrentPosition, LOCATIONS, OwnedLocation, Region, regions};
+
+/// TOML documents need a table at the top level
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Wrapper<T> {
+    value: T,
+}
+
+fn assert_matches_catalog(owned: &OwnedLocation, index: usize) {
+    let location = &LOCATIONS[index];
+    assert_eq!(owned.name, location.name);
+    assert_eq!(owned.region, location.region);
+    assert_eq!(owned.x.to_bits(), location.x.to_bits());
+    assert_eq!(owned.y.to_bits(), location.y.to_bits());
+    assert_eq!(owned.z.to_bits(), location.z.to_bits());
+    assert_eq!(owned.map_id, location.map_id);
+    assert_eq!(owned.facing, location.facing);
+}
+
+#[test]
+fn every_catalog_location_round_trips_through_json() {
+    for (index, location) in LOCATIONS.iter().enumerate() {
+        let owned = OwnedLocation::from(location);
+        let json = serde_json::to_string(&owned).unwrap();
+        let back: OwnedLocation = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(back, owned, "{} changed in JSON: {}", location.name, json);
+        assert_matches_catalog(&back, index);
+    }
+}
+
+#[test]
+fn every_catalog_location_round_trips_through_toml() {
+    for (index, location) in LOCATIONS.iter().enumerate() {
+        let owned = Wrapper {
+            value: OwnedLocation::from(location),
+        };
+        let text = toml::to_string(&owned).unwrap();
+        let back: Wrapper<OwnedLocation> = toml::from_str(&text).unwrap();
+
+        assert_eq!(back, owned, "{} changed in TOML: {}", location.name, text);
+        assert_matches_catalog(&back.value, index);
+    }
+}
+
+#[test]
+fn borrowed_and_owned_locations_serialize_identically() {
+    for location in LOCATIONS.iter() {
+        let borrowed = serde_json::to_value(location).unwrap();
+        let owned = serde_json::to_value(OwnedLocation::from(location)).unwrap();
+        assert_eq!(borrowed, owned, "{}", location.name);
+    }
+}
+
+#[test]
+fn regions_round_trip_through_json() {
+    let regions = regions();
+    let json = serde_json::to_string(&regions).unwrap();
+    let back: Vec<Region> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(back, regions);
+    assert_eq!(
+        back.iter().map(|r| r.locations.len()).sum::<usize>(),
+        LOCATIONS.len()
+    );
+}
+
+#[test]
+fn current_position_round_trips_through_json_and_toml() {
+    let position = CurrentPosition {
+        x: -199.74,
+        y: -50.759,
+        z: 179.42,
+        map_id: [0x00, 0x00, 0x01, 0x18],
+    };
+
+    let json = serde_json::to_string(&position).unwrap();
+    assert_eq!(
+        serde_json::from_str::<CurrentPosition>(&json).unwrap(),
+        position
+    );
+
+    let wrapped = Wrapper {
+        value: position.clone(),
+    };
+    let text = toml::to_string(&wrapped).unwrap();
+    assert_eq!(
+        toml::from_str::<Wrapper<CurrentPosition>>(&text).unwrap(),
+        wrapped
+    );
+}
+
+#[test]
+fn unknown_fields_are_rejected() {
+    let json = r#"{"name":"A","region":"B","x":1.0,"y":2.0,"z":3.0,"map_id":[0,21],"extra":1}"#;
+    assert!(serde_json::from_str::<OwnedLocation>(json).is_err());
+}
@@ -0,0 +1,97 @@
+//! Scanning a save directory for character slots
+
+use lantern_teleport_core::{MapArea, TeleportError, scan_slots};
+
+mod common;
+use common::{COORDS, Scratch, put_f32s, save};
+
+/// [`save`] with the character standing at `x` in Cathedral Ward
+fn save_at(x: f32) -> Vec<u8> {
+    let mut bytes = save();
+    bytes[0x04..0x08].copy_from_slice(&[0x00, 0x00, 0x00, 0x18]);
+    put_f32s(&mut bytes, COORDS, &[x, 0.0, 0.0]);
+    bytes
+}
+
+#[test]
+fn slots_are_returned_in_index_order() {
+    let scratch = Scratch::new("ordered");
+    scratch.write("userdata0010", &save_at(10.0));
+    scratch.write("userdata0000", &save_at(0.0));
+    scratch.write("userdata0002", &save());
+
+    let slots = scan_slots(&scratch.dir).unwrap();
+
+    let indices: Vec<usize> = slots.iter().map(|slot| slot.index).collect();
+    assert_eq!(indices, vec![0, 2, 10]);
+    assert_eq!(slots[0].path, scratch.dir.join("userdata0000"));
+    assert_eq!(slots[0].position.as_ref().unwrap().x, 0.0);
+    assert_eq!(slots[2].position.as_ref().unwrap().x, 10.0);
+    assert_eq!(
+        slots[1].info.as_ref().unwrap().map.area(),
+        MapArea::HuntersDream
+    );
+    assert_eq!(slots[2].info.as_ref().unwrap().map.area(), MapArea::Yharnam);
+    assert!(
+        slots
+            .iter()
+            .all(|slot| slot.is_valid() && slot.character.is_some())
+    );
+}
+
+#[test]
+fn unparseable_slots_are_returned_with_their_error() {
+    let scratch = Scratch::new("unparseable");
+    scratch.write("userdata0000", &save());
+    scratch.write("userdata0001", b"too small");
+    scratch.write("userdata0002", &vec![0u8; 0x100]);
+
+    let slots = scan_slots(&scratch.dir).unwrap();
+
+    assert_eq!(slots.len(), 3);
+    assert!(slots[0].is_valid());
+    for slot in &slots[1..] {
+        assert!(!slot.is_valid());
+        assert!(slot.info.is_none());
+        assert!(slot.character.is_none());
+    }
+    assert!(matches!(
+        slots[1].position,
+        Err(TeleportError::FileTooSmall { .. })
+    ));
+    assert!(matches!(
+        slots[2].position,
+        Err(TeleportError::LcedMarkerNotFound { .. })
+    ));
+}
+
+#[test]
+fn only_slot_files_are_scanned() {
+    let scratch = Scratch::new("names");
+    scratch.write("userdata0001", &save());
+    for name in [
+        "userdata001",
+        "userdata00001",
+        "userdata00a1",
+        "userdata0001.bak",
+        "Userdata0002",
+        "param.sfo",
+    ] {
+        scratch.write(name, &save());
+    }
+    std::fs::create_dir(scratch.dir.join("userdata0003")).unwrap();
+
+    let slots = scan_slots(&scratch.dir).unwrap();
+
+    assert_eq!(slots.len(), 1);
+    assert_eq!(slots[0].index, 1);
+}
+
+#[test]
+fn missing_directories_cannot_be_scanned() {
+    let scratch = Scratch::new("missing");
+    assert!(matches!(
+        scan_slots(scratch.dir.join("missing")),
+        Err(TeleportError::ReadError { path: Some(_), .. })
+    ));
+}
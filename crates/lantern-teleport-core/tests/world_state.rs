@@ -0,0 +1,64 @@
+//! World state read from the night and Blood Moon flags
+
+#![cfg(feature = "experimental")]
+
+use lantern_teleport_core::{
+    SaveFile, TeleportWarning, WorldState, teleport_with_options, world_state_bytes,
+};
+
+mod common;
+use common::{LCED, Scratch, location, save};
+
+/// Byte of the event flag block holding flags 56 (night) and 57 (Blood Moon)
+const STATE_FLAGS: usize = LCED + 0x38 + 7;
+
+#[test]
+fn world_state_follows_the_story_flags() {
+    let mut bytes = save();
+    assert_eq!(world_state_bytes(&bytes).unwrap(), WorldState::Evening);
+    bytes[STATE_FLAGS] = 0b1000_0000;
+    assert_eq!(world_state_bytes(&bytes).unwrap(), WorldState::Night);
+    bytes[STATE_FLAGS] = 0b1100_0000;
+    assert_eq!(world_state_bytes(&bytes).unwrap(), WorldState::BloodMoon);
+}
+
+#[test]
+fn early_variants_get_a_warning() {
+    let mut bytes = save();
+    bytes[STATE_FLAGS] = 0b1000_0000;
+    let save = SaveFile::from_bytes(bytes).unwrap();
+
+    assert_eq!(
+        save.world_state_warning(&location("Moonside Lake").map_id),
+        Some(TeleportWarning::WorldStateMismatch {
+            area: "Moonside Lake",
+            required: WorldState::BloodMoon,
+            actual: WorldState::Night,
+        })
+    );
+    assert_eq!(
+        save.world_state_warning(&location("Upper Cathedral Ward").map_id),
+        None
+    );
+    assert_eq!(
+        save.world_state_warning(&location("Byrgenwerth").map_id),
+        None
+    );
+}
+
+#[test]
+fn teleports_report_the_warning_and_still_write() {
+    let scratch = Scratch::new("world-state");
+    let path = scratch.write("userdata0000", &save());
+    let outcome = teleport_with_options(&path, location("Moonside Lake"), &scratch.options);
+    let position = SaveFile::load(&path).and_then(|save| save.position());
+
+    assert!(
+        outcome
+            .unwrap()
+            .warnings
+            .iter()
+            .any(|warning| matches!(warning, TeleportWarning::WorldStateMismatch { .. }))
+    );
+    assert_eq!(position.unwrap().map_id, [0x00, 0x00, 0x02, 0x20]);
+}
@@ -3,18 +3,29 @@
 use std::path::PathBuf;
 
 use lantern_teleport_core::{
-    CurrentPosition, Location, get_all_locations, teleport, validate_save_file,
+    BackupInfo, Bookmark, BookmarkStore, CharacterInfo, CurrentPosition, LanternStatus, Location,
+    MapId, SaveFile, SaveSlot, TeleportError, TeleportOptions, TeleportOutcome, TeleportWarning,
+    WorldState, copy_position, locations_by_region, nudge, scan_slots, teleport_with_options,
+    undo_last_teleport,
 };
 
+/// Distance a single nudge key press moves the character along its axis
+pub const NUDGE_STEP: f32 = 0.5;
+
 #[derive(Debug, Clone)]
 pub enum AppMode {
     FileBrowser,
+    SlotPicker,
+    CopySourcePicker,
+    CopyConfirmation,
     Validating,
     ValidationSuccess(CurrentPosition),
+    BookmarkInput(CurrentPosition),
     ValidationError(String),
     LocationSelection,
     LocationSearch,
     Confirmation,
+    FacingInput,
     Teleporting,
     TeleportSuccess,
     TeleportError(String),
@@ -32,13 +43,27 @@ pub struct App {
     pub file_list: Vec<FileEntry>,
     pub selected_file: usize,
     pub file_scroll_offset: usize,
+    pub slots: Vec<SaveSlot>,
+    pub selected_slot: usize,
     pub location_groups: Vec<LocationGroup>,
     pub filtered_location_groups: Vec<LocationGroup>,
     pub selected_location: usize,
     pub search_query: String,
     pub save_file_path: Option<PathBuf>,
     pub current_position: Option<CurrentPosition>,
+    pub character: Option<CharacterInfo>,
+    pub lanterns: Vec<LanternStatus>,
+    pub dlc_progress: Option<bool>,
+    pub world_state: Option<WorldState>,
     pub selected_destination: Option<&'static Location>,
+    pub copy_source: Option<PathBuf>,
+    pub nudge: [f32; 3],
+    pub last_backup: Option<BackupInfo>,
+    pub undoable: bool,
+    pub bookmark_name: String,
+    pub facing_input: String,
+    pub facing_override: Option<f32>,
+    pub status_message: Option<String>,
     pub confirm_selection: bool,
     pub should_quit: bool,
 }
@@ -61,34 +86,37 @@ impl App {
             file_list: Vec::new(),
             selected_file: 0,
             file_scroll_offset: 0,
+            slots: Vec::new(),
+            selected_slot: 0,
             location_groups: location_groups.clone(),
             filtered_location_groups: location_groups,
             selected_location: 0,
             search_query: String::new(),
             save_file_path: None,
             current_position: None,
+            character: None,
+            lanterns: Vec::new(),
+            dlc_progress: None,
+            world_state: None,
             selected_destination: None,
+            copy_source: None,
+            nudge: [0.0; 3],
+            last_backup: None,
+            undoable: false,
+            bookmark_name: String::new(),
+            facing_input: String::new(),
+            facing_override: None,
+            status_message: None,
             confirm_selection: false,
             should_quit: false,
         }
     }
 
     fn group_locations_by_region() -> Vec<LocationGroup> {
-        let locations = get_all_locations();
-        let mut groups: Vec<LocationGroup> = Vec::new();
-
-        for location in locations {
-            if let Some(existing) = groups.iter_mut().find(|g| g.region == location.region) {
-                existing.locations.push(location);
-            } else {
-                groups.push(LocationGroup {
-                    region: location.region,
-                    locations: vec![location],
-                });
-            }
-        }
-
-        groups
+        locations_by_region()
+            .into_iter()
+            .map(|(region, locations)| LocationGroup { region, locations })
+            .collect()
     }
 
     pub fn refresh_file_list(&mut self) {
@@ -130,8 +158,8 @@ impl App {
                 }
             }
 
-            dirs.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-            files.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+            dirs.sort_by_key(|entry| entry.name.to_lowercase());
+            files.sort_by_key(|entry| entry.name.to_lowercase());
 
             self.file_list.extend(dirs);
             self.file_list.extend(files);
@@ -151,20 +179,251 @@ impl App {
         }
     }
 
-    pub fn validate_save_file(&mut self) {
-        if let Some(ref path) = self.save_file_path {
-            match validate_save_file(path) {
-                Ok(position) => {
-                    self.current_position = Some(position.clone());
-                    self.mode = AppMode::ValidationSuccess(position);
-                }
-                Err(e) => {
-                    self.mode = AppMode::ValidationError(format!("{:?}", e));
+    /// Scan the current directory for character slots and show the slot picker
+    pub fn open_slot_picker(&mut self) {
+        self.selected_slot = 0;
+        match scan_slots(&self.current_path) {
+            Ok(slots) => {
+                self.slots = slots;
+                self.status_message = None;
+            }
+            Err(e) => {
+                self.slots.clear();
+                self.status_message = Some(e.to_string());
+            }
+        }
+        self.mode = AppMode::SlotPicker;
+    }
+
+    pub fn move_slot_up(&mut self) {
+        if self.selected_slot > 0 {
+            self.selected_slot -= 1;
+        }
+    }
+
+    pub fn move_slot_down(&mut self) {
+        if self.selected_slot < self.slots.len().saturating_sub(1) {
+            self.selected_slot += 1;
+        }
+    }
+
+    /// Validate the save file of the selected slot
+    pub fn select_slot(&mut self) {
+        if let Some(slot) = self.slots.get(self.selected_slot) {
+            self.save_file_path = Some(slot.path.clone());
+            self.status_message = None;
+            self.mode = AppMode::Validating;
+        }
+    }
+
+    /// Leave the slot picker without choosing a slot
+    pub fn close_slot_picker(&mut self) {
+        self.slots.clear();
+        self.status_message = None;
+        self.mode = AppMode::FileBrowser;
+    }
+
+    /// Show the other character slots of the current directory to copy a
+    /// position from
+    pub fn open_copy_source_picker(&mut self) {
+        let Some(path) = self.save_file_path.clone() else {
+            return;
+        };
+        // Save files are always picked from the current directory
+        self.selected_slot = 0;
+        match scan_slots(&self.current_path) {
+            Ok(slots) => {
+                self.slots = slots
+                    .into_iter()
+                    .filter(|slot| slot.path.file_name() != path.file_name())
+                    .collect();
+                self.status_message = None;
+            }
+            Err(e) => {
+                self.slots.clear();
+                self.status_message = Some(e.to_string());
+            }
+        }
+        self.mode = AppMode::CopySourcePicker;
+    }
+
+    /// Ask to copy the position of the selected slot, if its save is valid
+    pub fn select_copy_source(&mut self) {
+        if let Some(slot) = self.slots.get(self.selected_slot)
+            && slot.is_valid()
+        {
+            self.copy_source = Some(slot.path.clone());
+            self.mode = AppMode::CopyConfirmation;
+        }
+    }
+
+    /// Leave the copy source picker and go back to the validation screen
+    pub fn close_copy_source_picker(&mut self) {
+        self.slots.clear();
+        self.copy_source = None;
+        self.status_message = None;
+        self.mode = match self.current_position.clone() {
+            Some(position) => AppMode::ValidationSuccess(position),
+            None => AppMode::FileBrowser,
+        };
+    }
+
+    /// Move the character to the position of the chosen source save
+    pub fn execute_copy(&mut self) {
+        let (Some(path), Some(source)) = (&self.save_file_path, &self.copy_source) else {
+            return;
+        };
+        match copy_position(source, path, &TeleportOptions::default()) {
+            Ok((_, outcome)) => {
+                self.selected_destination = None;
+                self.show_outcome(outcome);
+                self.refresh_after_edit();
+                self.mode = AppMode::TeleportSuccess;
+            }
+            Err(e) => {
+                self.copy_source = None;
+                self.mode = AppMode::TeleportError(e.to_string());
+            }
+        }
+    }
+
+    /// Step the pending nudge along an axis (0 = X, 1 = Y, 2 = Z) by
+    /// `steps` times [`NUDGE_STEP`]
+    pub fn step_nudge(&mut self, axis: usize, steps: f32) {
+        if let Some(offset) = self.nudge.get_mut(axis) {
+            *offset += steps * NUDGE_STEP;
+        }
+    }
+
+    /// Whether a nudge has been stepped but not written yet
+    pub fn has_pending_nudge(&self) -> bool {
+        self.nudge != [0.0; 3]
+    }
+
+    /// Where the pending nudge would move the character
+    pub fn nudged_position(&self) -> Option<CurrentPosition> {
+        let mut position = self.current_position.clone()?;
+        position.x += self.nudge[0];
+        position.y += self.nudge[1];
+        position.z += self.nudge[2];
+        Some(position)
+    }
+
+    /// Forget the pending nudge
+    pub fn discard_nudge(&mut self) {
+        self.nudge = [0.0; 3];
+    }
+
+    /// Write the pending nudge to the save file
+    pub fn apply_nudge(&mut self) {
+        let Some(path) = &self.save_file_path else {
+            return;
+        };
+        if !self.has_pending_nudge() {
+            return;
+        }
+        let [dx, dy, dz] = self.nudge;
+        match nudge(path, dx, dy, dz, &TeleportOptions::default()) {
+            Ok((position, outcome)) => {
+                self.last_backup = outcome.backup;
+                self.nudge = [0.0; 3];
+                let mut message = format!("Nudged by X {:+.2}  Y {:+.2}  Z {:+.2}", dx, dy, dz);
+                for warning in &outcome.warnings {
+                    message.push_str(&format!("; {}", warning));
                 }
+                self.status_message = Some(message);
+                self.current_position = Some(position.clone());
+                self.refresh_after_edit();
+                self.mode = AppMode::ValidationSuccess(position);
+            }
+            Err(e) => self.status_message = Some(format!("Nudge failed: {}", e)),
+        }
+    }
+
+    pub fn validate_save_file(&mut self) {
+        if self.save_file_path.is_some() {
+            match self.reload_save() {
+                Ok(position) => self.mode = AppMode::ValidationSuccess(position),
+                Err(e) => self.mode = AppMode::ValidationError(e.to_string()),
             }
         }
     }
 
+    /// Read the position, character, lanterns and world state of the save
+    fn reload_save(&mut self) -> Result<CurrentPosition, TeleportError> {
+        let Some(path) = &self.save_file_path else {
+            return Err(TeleportError::NoSavePath);
+        };
+        let save = SaveFile::load(path)?;
+        let position = save.position()?;
+        self.current_position = Some(position.clone());
+        self.character = Some(save.character());
+        self.dlc_progress = save.has_dlc_progress().ok();
+        // Lantern and world state flags are unverified, so locks and world
+        // state are only shown when built with the experimental feature; saves
+        // without readable flags show neither
+        #[cfg(feature = "experimental")]
+        {
+            self.lanterns = save.lantern_statuses().unwrap_or_default();
+            self.world_state = save.world_state().ok();
+        }
+        Ok(position)
+    }
+
+    /// Re-read the save after an edit, so the position, locks and world state
+    /// shown match what was written
+    fn refresh_after_edit(&mut self) {
+        if let Err(e) = self.reload_save() {
+            let note = format!("could not re-read the save: {}", e);
+            self.status_message = Some(match self.status_message.take() {
+                Some(message) => format!("{}; {}", message, note),
+                None => note,
+            });
+        }
+    }
+
+    /// Whether the save is known not to have lit the location's lantern
+    pub fn is_locked(&self, location: &Location) -> bool {
+        self.lanterns
+            .iter()
+            .any(|status| status.location.name == location.name && status.is_locked())
+    }
+
+    /// Start naming a bookmark for the validated position
+    pub fn start_bookmark_input(&mut self) {
+        if let AppMode::ValidationSuccess(position) = &self.mode {
+            self.bookmark_name.clear();
+            self.status_message = None;
+            self.mode = AppMode::BookmarkInput(position.clone());
+        }
+    }
+
+    /// Save the validated position as a bookmark under the typed name
+    pub fn save_bookmark(&mut self) {
+        if let AppMode::BookmarkInput(position) = &self.mode {
+            let position = position.clone();
+            let bookmark = Bookmark::from_position(self.bookmark_name.trim(), &position);
+
+            let result = BookmarkStore::load_default().and_then(|mut store| {
+                store.add(bookmark)?;
+                store.save()
+            });
+
+            self.status_message = Some(match result {
+                Ok(()) => format!("Bookmark '{}' saved", self.bookmark_name.trim()),
+                Err(e) => format!("Failed to save bookmark: {}", e),
+            });
+            self.mode = AppMode::ValidationSuccess(position);
+        }
+    }
+
+    /// Abandon naming a bookmark
+    pub fn cancel_bookmark_input(&mut self) {
+        if let AppMode::BookmarkInput(position) = &self.mode {
+            self.mode = AppMode::ValidationSuccess(position.clone());
+        }
+    }
+
     pub fn move_file_up(&mut self) {
         if self.selected_file > 0 {
             self.selected_file -= 1;
@@ -253,11 +512,63 @@ impl App {
     pub fn select_location(&mut self) {
         if let Some(location) = self.get_selected_location() {
             self.selected_destination = Some(location);
+            self.facing_override = None;
             self.confirm_selection = false;
             self.mode = AppMode::Confirmation;
         }
     }
 
+    /// Whether the destination is in The Old Hunters DLC and the save shows
+    /// no DLC progress
+    pub fn lacks_dlc_progress(&self, location: &Location) -> bool {
+        let is_dlc = MapId::from_bytes(location.map_id).is_ok_and(MapId::is_dlc);
+        is_dlc && self.dlc_progress == Some(false)
+    }
+
+    /// The world state the destination's map variant needs, if the save has
+    /// not reached it yet
+    pub fn missing_world_state(&self, location: &Location) -> Option<WorldState> {
+        let required = MapId::from_bytes(location.map_id)
+            .ok()?
+            .required_world_state();
+        self.world_state
+            .filter(|state| *state < required)
+            .map(|_| required)
+    }
+
+    /// Facing the character will have on arrival. The facing offset is
+    /// unverified, so only a typed facing is written; the destination's
+    /// default is merely suggested in the input.
+    pub fn destination_facing(&self) -> Option<f32> {
+        self.facing_override
+    }
+
+    /// Start typing a facing for the destination, starting from the typed one
+    /// or the destination's default
+    pub fn start_facing_input(&mut self) {
+        self.facing_input = self
+            .facing_override
+            .or_else(|| {
+                self.selected_destination
+                    .and_then(|location| location.facing)
+            })
+            .map(|facing| format!("{:.0}", facing))
+            .unwrap_or_default();
+        self.mode = AppMode::FacingInput;
+    }
+
+    /// Use the typed facing, or keep the current facing if the input is
+    /// empty. Input that is not a finite number is ignored.
+    pub fn apply_facing_input(&mut self) {
+        let input = self.facing_input.trim();
+        if input.is_empty() {
+            self.facing_override = None;
+        } else if let Some(facing) = input.parse::<f32>().ok().filter(|f| f.is_finite()) {
+            self.facing_override = Some(facing);
+        }
+        self.mode = AppMode::Confirmation;
+    }
+
     pub fn move_confirm_left(&mut self) {
         self.confirm_selection = false;
     }
@@ -271,12 +582,24 @@ impl App {
             if let (Some(path), Some(location)) = (&self.save_file_path, self.selected_destination)
             {
                 self.mode = AppMode::Teleporting;
-                match teleport(path, location) {
-                    Ok(()) => {
+                let destination = match self.facing_override {
+                    Some(facing) => location.clone().with_facing(facing),
+                    None => Ok(location.clone()),
+                };
+                let options = TeleportOptions {
+                    write_facing: self.facing_override.is_some(),
+                    ..TeleportOptions::default()
+                };
+                match destination
+                    .and_then(|destination| teleport_with_options(path, &destination, &options))
+                {
+                    Ok(outcome) => {
+                        self.show_outcome(outcome);
+                        self.refresh_after_edit();
                         self.mode = AppMode::TeleportSuccess;
                     }
                     Err(e) => {
-                        self.mode = AppMode::TeleportError(format!("{:?}", e));
+                        self.mode = AppMode::TeleportError(e.to_string());
                     }
                 }
             }
@@ -285,11 +608,56 @@ impl App {
         }
     }
 
+    /// Keep the backup of a written edit, show its warnings and offer undo
+    /// only if the edit made it into the journal
+    fn show_outcome(&mut self, outcome: TeleportOutcome) {
+        self.last_backup = outcome.backup;
+        self.undoable = !outcome
+            .warnings
+            .iter()
+            .any(|warning| matches!(warning, TeleportWarning::NotJournaled(_)));
+        self.status_message = (!outcome.warnings.is_empty()).then(|| {
+            outcome
+                .warnings
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("; ")
+        });
+    }
+
+    /// Undo the teleport just made, restoring the previous position and map
+    pub fn undo_teleport(&mut self) {
+        let Some(path) = &self.save_file_path else {
+            return;
+        };
+        match undo_last_teleport(path, &TeleportOptions::default()) {
+            Ok((undone, outcome)) => {
+                self.last_backup = outcome.backup;
+                self.undoable = false;
+                self.status_message = Some(format!("Undid the teleport to {}", undone.target));
+                self.selected_destination = None;
+                self.copy_source = None;
+                self.refresh_after_edit();
+                self.mode = AppMode::LocationSelection;
+            }
+            Err(e) => self.status_message = Some(format!("Undo failed: {}", e)),
+        }
+    }
+
     pub fn go_back_to_file_browser(&mut self) {
         self.mode = AppMode::FileBrowser;
         self.save_file_path = None;
         self.current_position = None;
+        self.character = None;
+        self.lanterns.clear();
+        self.dlc_progress = None;
+        self.world_state = None;
         self.selected_destination = None;
+        self.copy_source = None;
+        self.nudge = [0.0; 3];
+        self.last_backup = None;
+        self.status_message = None;
         self.search_query.clear();
         self.selected_location = 0;
         self.apply_search_filter();
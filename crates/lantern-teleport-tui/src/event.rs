@@ -1,6 +1,6 @@
 //! Event handling for the TUI application
 
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
 use std::time::Duration;
 
 /// Internal representation of key actions for the TUI
@@ -99,12 +99,26 @@ impl KeyAction {
             _ => None,
         }
     }
+
+    /// Convert a KeyEvent into a KeyAction while typing free text
+    ///
+    /// Letters are always treated as input here, so 'q' and hjkl can be typed
+    /// without quitting or moving the selection. Ctrl+C still quits.
+    pub fn from_text_input_event(key: KeyEvent) -> Option<Self> {
+        match key {
+            KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+                ..
+            } => Some(KeyAction::Char(c)),
+            _ => Self::from_key_event(key),
+        }
+    }
 }
 
 pub enum TerminalEvent {
     Key(KeyEvent),
-    Mouse(MouseEvent),
-    Resize(u16, u16),
+    Resize,
     Tick,
 }
 
@@ -125,8 +139,7 @@ impl EventHandler {
         if event::poll(self.tick_rate)? {
             match event::read()? {
                 Event::Key(key) => Ok(TerminalEvent::Key(key)),
-                Event::Mouse(mouse) => Ok(TerminalEvent::Mouse(mouse)),
-                Event::Resize(width, height) => Ok(TerminalEvent::Resize(width, height)),
+                Event::Resize(_, _) => Ok(TerminalEvent::Resize),
                 _ => Ok(TerminalEvent::Tick),
             }
         } else {
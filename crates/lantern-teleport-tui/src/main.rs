@@ -13,12 +13,21 @@ use crossterm::{
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
+use lantern_teleport_core::Catalog;
 use ratatui::{Terminal, backend::CrosstermBackend};
 
 use app::{App, AppMode};
 use event::{EventHandler, KeyAction, TerminalEvent};
 
 fn main() -> std::io::Result<()> {
+    // Load extra locations before the terminal is taken over, so errors stay visible
+    let mut catalog = Catalog::builtin();
+    if let Err(e) = catalog.load_user_catalogs() {
+        eprintln!("Warning: Ignoring user catalogs: {}", e);
+        catalog = Catalog::builtin();
+    }
+    let _ = catalog.install();
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = std::io::stdout();
@@ -45,7 +54,7 @@ fn main() -> std::io::Result<()> {
     terminal.show_cursor()?;
 
     if let Err(err) = res {
-        eprintln!("Error: {:?}", err);
+        eprintln!("Error: {}", err);
     }
 
     Ok(())
@@ -64,7 +73,12 @@ fn run_app(
         match events.next()? {
             TerminalEvent::Key(key) => {
                 // Convert to KeyAction
-                let action = KeyAction::from_key_event(key);
+                let action = if matches!(app.mode, AppMode::BookmarkInput(_) | AppMode::FacingInput)
+                {
+                    KeyAction::from_text_input_event(key)
+                } else {
+                    KeyAction::from_key_event(key)
+                };
 
                 // Global quit handler
                 if matches!(action, Some(KeyAction::Quit)) {
@@ -74,18 +88,23 @@ fn run_app(
                 // Mode-specific handlers
                 match &app.mode {
                     AppMode::FileBrowser => handle_file_browser_input(app, action),
+                    AppMode::SlotPicker => handle_slot_picker_input(app, action),
+                    AppMode::CopySourcePicker => handle_copy_source_picker_input(app, action),
+                    AppMode::CopyConfirmation => handle_copy_confirmation_input(app, action),
                     AppMode::Validating => {}
                     AppMode::ValidationSuccess(_) => handle_validation_success_input(app, action),
+                    AppMode::BookmarkInput(_) => handle_bookmark_input(app, action),
                     AppMode::ValidationError(_) => handle_validation_error_input(app, action),
                     AppMode::LocationSelection => handle_location_selection_input(app, action),
                     AppMode::LocationSearch => handle_location_search_input(app, action),
                     AppMode::Confirmation => handle_confirmation_input(app, action),
+                    AppMode::FacingInput => handle_facing_input(app, action),
                     AppMode::Teleporting => {}
                     AppMode::TeleportSuccess => handle_teleport_success_input(app, action),
                     AppMode::TeleportError(_) => handle_teleport_error_input(app, action),
                 }
             }
-            TerminalEvent::Resize(_, _) => {
+            TerminalEvent::Resize => {
                 // Terminal resized, will redraw on next iteration
             }
             _ => {}
@@ -111,14 +130,27 @@ fn run_app(
 fn render_frame(f: &mut ratatui::Frame, app: &App) {
     match &app.mode {
         AppMode::FileBrowser => ui::render_file_browser(f, app),
+        AppMode::SlotPicker | AppMode::CopySourcePicker => ui::render_slot_picker(f, app),
+        AppMode::CopyConfirmation => {
+            ui::render_slot_picker(f, app);
+            ui::render_copy_confirmation(f, app);
+        }
         AppMode::Validating => {
             // Show a loading message
             render_loading(f, "Validating save file...");
         }
         AppMode::ValidationSuccess(position) => ui::render_validation_success(f, app, position),
+        AppMode::BookmarkInput(position) => {
+            ui::render_validation_success(f, app, position);
+            ui::render_bookmark_input(f, app);
+        }
         AppMode::ValidationError(error) => ui::render_validation_error(f, error),
         AppMode::LocationSelection | AppMode::LocationSearch => ui::render_location_list(f, app),
         AppMode::Confirmation => ui::render_confirmation(f, app),
+        AppMode::FacingInput => {
+            ui::render_confirmation(f, app);
+            ui::render_facing_input(f, app);
+        }
         AppMode::Teleporting => {
             render_loading(f, "Teleporting...");
         }
@@ -153,14 +185,73 @@ fn handle_file_browser_input(app: &mut App, action: Option<KeyAction>) {
         Some(KeyAction::Up) => app.move_file_up(),
         Some(KeyAction::Down) => app.move_file_down(),
         Some(KeyAction::Enter) => app.navigate_to_selected(),
+        Some(KeyAction::Char('s')) => app.open_slot_picker(),
+        _ => {}
+    }
+}
+
+fn handle_slot_picker_input(app: &mut App, action: Option<KeyAction>) {
+    match action {
+        Some(KeyAction::Up) => app.move_slot_up(),
+        Some(KeyAction::Down) => app.move_slot_down(),
+        Some(KeyAction::Enter) => app.select_slot(),
+        Some(KeyAction::Escape) => app.close_slot_picker(),
+        _ => {}
+    }
+}
+
+fn handle_copy_source_picker_input(app: &mut App, action: Option<KeyAction>) {
+    match action {
+        Some(KeyAction::Up) => app.move_slot_up(),
+        Some(KeyAction::Down) => app.move_slot_down(),
+        Some(KeyAction::Enter) => app.select_copy_source(),
+        Some(KeyAction::Escape) => app.close_copy_source_picker(),
+        _ => {}
+    }
+}
+
+fn handle_copy_confirmation_input(app: &mut App, action: Option<KeyAction>) {
+    match action {
+        Some(KeyAction::Enter) => app.execute_copy(),
+        Some(KeyAction::Escape) => {
+            app.copy_source = None;
+            app.mode = AppMode::CopySourcePicker;
+        }
         _ => {}
     }
 }
 
 fn handle_validation_success_input(app: &mut App, action: Option<KeyAction>) {
     match action {
-        Some(KeyAction::Enter) => app.mode = AppMode::LocationSelection,
+        Some(KeyAction::Enter) => {
+            // Bookmark and nudge messages belong to the validation screen
+            app.status_message = None;
+            app.discard_nudge();
+            app.mode = AppMode::LocationSelection;
+        }
+        Some(KeyAction::Escape) if app.has_pending_nudge() => app.discard_nudge(),
         Some(KeyAction::Escape) => app.go_back_to_file_browser(),
+        Some(KeyAction::Char('b')) => app.start_bookmark_input(),
+        Some(KeyAction::Char('c')) => app.open_copy_source_picker(),
+        Some(KeyAction::Char('x')) => app.step_nudge(0, 1.0),
+        Some(KeyAction::Char('X')) => app.step_nudge(0, -1.0),
+        Some(KeyAction::Char('y')) => app.step_nudge(1, 1.0),
+        Some(KeyAction::Char('Y')) => app.step_nudge(1, -1.0),
+        Some(KeyAction::Char('z')) => app.step_nudge(2, 1.0),
+        Some(KeyAction::Char('Z')) => app.step_nudge(2, -1.0),
+        Some(KeyAction::Char('w')) => app.apply_nudge(),
+        _ => {}
+    }
+}
+
+fn handle_bookmark_input(app: &mut App, action: Option<KeyAction>) {
+    match action {
+        Some(KeyAction::Enter) if !app.bookmark_name.trim().is_empty() => app.save_bookmark(),
+        Some(KeyAction::Escape) => app.cancel_bookmark_input(),
+        Some(KeyAction::Backspace) => {
+            app.bookmark_name.pop();
+        }
+        Some(KeyAction::Char(c)) => app.bookmark_name.push(c),
         _ => {}
     }
 }
@@ -210,6 +301,21 @@ fn handle_confirmation_input(app: &mut App, action: Option<KeyAction>) {
         Some(KeyAction::Right) => app.move_confirm_right(),
         Some(KeyAction::Enter) => app.mode = AppMode::Teleporting,
         Some(KeyAction::Escape) => app.mode = AppMode::LocationSelection,
+        Some(KeyAction::Char('a')) => app.start_facing_input(),
+        _ => {}
+    }
+}
+
+fn handle_facing_input(app: &mut App, action: Option<KeyAction>) {
+    match action {
+        Some(KeyAction::Enter) => app.apply_facing_input(),
+        Some(KeyAction::Escape) => app.mode = AppMode::Confirmation,
+        Some(KeyAction::Backspace) => {
+            app.facing_input.pop();
+        }
+        Some(KeyAction::Char(c)) if c.is_ascii_digit() || c == '.' || c == '-' => {
+            app.facing_input.push(c)
+        }
         _ => {}
     }
 }
@@ -220,8 +326,11 @@ fn handle_teleport_success_input(app: &mut App, action: Option<KeyAction>) {
             // Go back to location selection for another teleport
             app.mode = AppMode::LocationSelection;
             app.selected_destination = None;
+            app.copy_source = None;
+            app.status_message = None;
         }
         Some(KeyAction::Escape) => app.go_back_to_file_browser(),
+        Some(KeyAction::Char('u')) if app.undoable => app.undo_teleport(),
         _ => {}
     }
 }
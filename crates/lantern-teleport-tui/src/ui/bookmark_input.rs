@@ -0,0 +1,57 @@
+//! Bookmark name input popup
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use super::centered_rect;
+use crate::app::App;
+
+/// Render the bookmark name popup on top of the current screen
+pub fn render_bookmark_input(f: &mut Frame, app: &App) {
+    let dialog_area = centered_rect(60, 30, f.area());
+    f.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Bookmark Current Position ")
+        .title_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+    let inner = block.inner(dialog_area);
+    f.render_widget(block, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(1), // Prompt
+            Constraint::Length(3), // Input
+            Constraint::Min(1),    // Footer
+        ])
+        .split(inner);
+
+    let prompt =
+        Paragraph::new("Enter a name for this bookmark:").style(Style::default().fg(Color::White));
+    f.render_widget(prompt, chunks[0]);
+
+    let input = Paragraph::new(format!("{}█", app.bookmark_name))
+        .style(Style::default().fg(Color::Yellow))
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(input, chunks[1]);
+
+    let footer = Paragraph::new(Line::from(vec![
+        Span::styled("Enter", Style::default().fg(Color::Cyan)),
+        Span::raw(": Save  "),
+        Span::styled("Esc", Style::default().fg(Color::Cyan)),
+        Span::raw(": Cancel"),
+    ]))
+    .alignment(Alignment::Center);
+    f.render_widget(footer, chunks[2]);
+}
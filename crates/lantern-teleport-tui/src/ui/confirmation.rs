@@ -2,14 +2,15 @@
 
 use ratatui::{
     Frame,
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 
+use super::centered_rect;
 use crate::app::App;
-use lantern_teleport_core::Location;
+use lantern_teleport_core::{Location, WorldState};
 
 /// Render the confirmation dialog
 pub fn render_confirmation(f: &mut Frame, app: &App) {
@@ -44,7 +45,13 @@ pub fn render_confirmation(f: &mut Frame, app: &App) {
 
     // Content
     if let Some(location) = app.selected_destination {
-        let content = build_confirmation_content(location);
+        let content = build_confirmation_content(
+            location,
+            app.is_locked(location),
+            app.lacks_dlc_progress(location),
+            app.missing_world_state(location).zip(app.world_state),
+            app.destination_facing(),
+        );
         let content_block = Paragraph::new(content)
             .alignment(Alignment::Center)
             .wrap(Wrap { trim: true })
@@ -94,6 +101,8 @@ pub fn render_confirmation(f: &mut Frame, app: &App) {
         Span::raw(": Switch  "),
         Span::styled("Enter", Style::default().fg(Color::Cyan)),
         Span::raw(": Select  "),
+        Span::styled("a", Style::default().fg(Color::Cyan)),
+        Span::raw(": Facing  "),
         Span::styled("Esc", Style::default().fg(Color::Cyan)),
         Span::raw(": Cancel  "),
         Span::styled("q", Style::default().fg(Color::Cyan)),
@@ -104,9 +113,16 @@ pub fn render_confirmation(f: &mut Frame, app: &App) {
     f.render_widget(footer, chunks[3]);
 }
 
-/// Build the confirmation content text
-fn build_confirmation_content(location: &Location) -> Vec<Line<'static>> {
-    vec![
+/// Build the confirmation content text. `world_state_mismatch` holds the
+/// world state the destination needs and the save's current one.
+fn build_confirmation_content(
+    location: &Location,
+    locked: bool,
+    lacks_dlc_progress: bool,
+    world_state_mismatch: Option<(WorldState, WorldState)>,
+    facing: Option<f32>,
+) -> Vec<Line<'static>> {
+    let mut lines = vec![
         Line::from(""),
         Line::from(Span::styled(
             "You are about to teleport to:",
@@ -126,36 +142,65 @@ fn build_confirmation_content(location: &Location) -> Vec<Line<'static>> {
             ),
             Style::default().fg(Color::Cyan),
         )),
+        Line::from(Span::styled(
+            match facing {
+                Some(facing) => format!("Facing: {:.0}°", facing),
+                None => "Facing: unchanged".to_string(),
+            },
+            Style::default().fg(Color::Cyan),
+        )),
         Line::from(""),
+    ];
+
+    if locked {
+        lines.push(Line::from(Span::styled(
+            "Warning: this character has not lit this lantern yet!",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(Span::styled(
+            "The area may be inaccessible or break quest progress.",
+            Style::default().fg(Color::Yellow),
+        )));
+        lines.push(Line::from(""));
+    }
+
+    if let Some((required, actual)) = world_state_mismatch {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "Warning: this map is only loaded from {} on, and this save is at {}.",
+                required, actual
+            ),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(Span::styled(
+            "The character may fall out of the world.",
+            Style::default().fg(Color::Yellow),
+        )));
+        lines.push(Line::from(""));
+    }
+
+    if lacks_dlc_progress {
+        lines.push(Line::from(Span::styled(
+            "Warning: this is an Old Hunters DLC area, and this save shows no DLC progress.",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(Span::styled(
+            "Without the DLC installed the game cannot load the character there.",
+            Style::default().fg(Color::Yellow),
+        )));
+        lines.push(Line::from(""));
+    }
+
+    lines.extend([
         Line::from(Span::styled(
             "This will modify your save file.",
             Style::default().fg(Color::Red),
         )),
         Line::from(Span::styled(
-            "Make sure you have a backup!",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            "A backup will be created automatically.",
+            Style::default().fg(Color::Green),
         )),
         Line::from(""),
-    ]
-}
-
-/// Helper function to create a centered rectangle
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(r);
-
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(popup_layout[1])[1]
+    ]);
+    lines
 }
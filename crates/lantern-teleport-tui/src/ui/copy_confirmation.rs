@@ -0,0 +1,106 @@
+//! Confirmation popup for copying a position between saves
+
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use super::{centered_rect, character_summary};
+use crate::app::App;
+use lantern_teleport_core::identify_position;
+
+/// Render the copy confirmation popup on top of the slot picker
+pub fn render_copy_confirmation(f: &mut Frame, app: &App) {
+    let dialog_area = centered_rect(60, 40, f.area());
+    f.render_widget(Clear, dialog_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Copy Position ")
+        .title_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+    let inner = block.inner(dialog_area);
+    f.render_widget(block, dialog_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Min(4),    // Source and target
+            Constraint::Length(1), // Footer
+        ])
+        .split(inner);
+
+    let file_name = |path: &std::path::Path| {
+        path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    };
+
+    let mut lines = Vec::new();
+    if let Some(slot) = app.slots.get(app.selected_slot) {
+        let who = slot
+            .character
+            .as_ref()
+            .map(character_summary)
+            .unwrap_or_else(|| "Unnamed".to_string());
+        lines.push(Line::from(vec![
+            Span::styled("From: ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                format!("{} ({})", who, file_name(&slot.path)),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+        if let Ok(position) = &slot.position {
+            let area = identify_position(position)
+                .map(|info| info.map.to_string())
+                .unwrap_or_else(|| "Unknown map".to_string());
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "{}  X: {:.2}  Y: {:.2}  Z: {:.2}",
+                    area, position.x, position.y, position.z
+                ),
+                Style::default().fg(Color::White),
+            )));
+        }
+    }
+    if let Some(path) = &app.save_file_path {
+        let who = app
+            .character
+            .as_ref()
+            .map(character_summary)
+            .unwrap_or_else(|| "Unnamed".to_string());
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("To:   ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                format!("{} ({})", who, file_name(path)),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    }
+
+    let content = Paragraph::new(lines)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    f.render_widget(content, chunks[0]);
+
+    let footer = Paragraph::new(Line::from(vec![
+        Span::styled("Enter", Style::default().fg(Color::Cyan)),
+        Span::raw(": Copy  "),
+        Span::styled("Esc", Style::default().fg(Color::Cyan)),
+        Span::raw(": Back"),
+    ]))
+    .alignment(Alignment::Center);
+    f.render_widget(footer, chunks[1]);
+}
@@ -72,6 +72,8 @@ pub fn render_file_browser(f: &mut Frame, app: &App) {
         Span::raw(": Navigate  "),
         Span::styled("Enter", Style::default().fg(Color::Cyan)),
         Span::raw(": Select  "),
+        Span::styled("s", Style::default().fg(Color::Cyan)),
+        Span::raw(": Character slots  "),
         Span::styled("q", Style::default().fg(Color::Cyan)),
         Span::raw(": Quit"),
     ]))
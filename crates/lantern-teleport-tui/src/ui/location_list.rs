@@ -23,8 +23,15 @@ pub fn render_location_list(f: &mut Frame, app: &App) {
         ])
         .split(f.area());
 
-    // Title block
-    let title = Paragraph::new("Select Destination").block(
+    // Title block, with the outcome of the last undo
+    let mut title_line = vec![Span::raw("Select Destination")];
+    if let Some(message) = &app.status_message {
+        title_line.push(Span::styled(
+            format!("  {}", message),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    let title = Paragraph::new(Line::from(title_line)).block(
         Block::default()
             .borders(Borders::ALL)
             .title(" Lantern Teleport TUI ")
@@ -133,9 +140,19 @@ fn build_location_items_with_selection(app: &App) -> (Vec<ListItem<'static>>, us
                 "(X: {:.2}, Y: {:.2}, Z: {:.2})",
                 location.x, location.y, location.z
             );
-            let text = format!("  {} {}", location.name, coords);
+            let item = if app.is_locked(location) {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("  {} {} ", location.name, coords),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled("(locked)", Style::default().fg(Color::Red)),
+                ]))
+            } else {
+                ListItem::new(format!("  {} {}", location.name, coords))
+            };
 
-            items.push(ListItem::new(text));
+            items.push(item);
             display_idx += 1;
             current_location_idx += 1;
         }
@@ -1,14 +1,66 @@
 //! UI rendering components
 
+mod bookmark_input;
 mod confirmation;
+mod copy_confirmation;
+mod facing_input;
 mod file_browser;
 mod location_list;
+mod slot_picker;
 mod status;
 
+pub use bookmark_input::render_bookmark_input;
 pub use confirmation::render_confirmation;
+pub use copy_confirmation::render_copy_confirmation;
+pub use facing_input::render_facing_input;
 pub use file_browser::render_file_browser;
 pub use location_list::render_location_list;
+pub use slot_picker::render_slot_picker;
 pub use status::{
     render_teleport_error, render_teleport_success, render_validation_error,
     render_validation_success,
 };
+
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+
+use lantern_teleport_core::CharacterInfo;
+
+/// Helper function to create a centered rectangle
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+/// One-line summary of a character: name, level and NG+ cycle
+fn character_summary(character: &CharacterInfo) -> String {
+    let mut parts = vec![
+        character
+            .name
+            .clone()
+            .unwrap_or_else(|| "Unnamed".to_string()),
+    ];
+    if let Some(level) = character.level {
+        parts.push(format!("Level {}", level));
+    }
+    match character.ng_cycle {
+        Some(0) => parts.push("NG".to_string()),
+        Some(cycle) => parts.push(format!("NG+{}", cycle)),
+        None => {}
+    }
+    parts.join("  ")
+}
@@ -0,0 +1,143 @@
+//! Character slot picker UI component
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use super::character_summary;
+use crate::app::{App, AppMode};
+use lantern_teleport_core::SaveSlot;
+
+/// Render the character slot picker for the current directory, also used to
+/// pick the save to copy a position from
+pub fn render_slot_picker(f: &mut Frame, app: &App) {
+    let copying = matches!(
+        app.mode,
+        AppMode::CopySourcePicker | AppMode::CopyConfirmation
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(2)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(10),   // Slot list
+            Constraint::Length(3), // Footer
+        ])
+        .split(f.area());
+
+    // Title block
+    let title_block = Block::default()
+        .borders(Borders::ALL)
+        .title(if copying {
+            " Lantern Teleport TUI - Copy Position From "
+        } else {
+            " Lantern Teleport TUI - Select Character Slot "
+        })
+        .title_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    let current_path = app.current_path.to_string_lossy();
+    let title_text = Paragraph::new(current_path.as_ref()).block(title_block);
+    f.render_widget(title_text, chunks[0]);
+
+    // Slot list, or why there is nothing to show
+    let list_block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Slots ({}) ", app.slots.len()));
+
+    if app.slots.is_empty() {
+        let message = app
+            .status_message
+            .clone()
+            .unwrap_or_else(|| "No character slots (userdata00NN) in this directory".to_string());
+        let paragraph = Paragraph::new(message)
+            .style(Style::default().fg(Color::Yellow))
+            .block(list_block);
+        f.render_widget(paragraph, chunks[1]);
+    } else {
+        let items: Vec<ListItem> = app.slots.iter().map(slot_item).collect();
+
+        let mut state = ListState::default();
+        state.select(Some(app.selected_slot));
+
+        let slot_list = List::new(items)
+            .block(list_block)
+            .highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("► ");
+
+        f.render_stateful_widget(slot_list, chunks[1], &mut state);
+    }
+
+    // Footer
+    let footer = Paragraph::new(Line::from(vec![
+        Span::styled("↑/↓", Style::default().fg(Color::Cyan)),
+        Span::raw(": Navigate  "),
+        Span::styled("Enter", Style::default().fg(Color::Cyan)),
+        Span::raw(if copying {
+            ": Copy position  "
+        } else {
+            ": Select  "
+        }),
+        Span::styled("Esc", Style::default().fg(Color::Cyan)),
+        Span::raw(": Back  "),
+        Span::styled("q", Style::default().fg(Color::Cyan)),
+        Span::raw(": Quit"),
+    ]))
+    .block(Block::default().borders(Borders::ALL));
+
+    f.render_widget(footer, chunks[2]);
+}
+
+/// One line per slot: its index and file name, then who the character is and
+/// where, or why the file is invalid
+fn slot_item(slot: &SaveSlot) -> ListItem<'static> {
+    let name = slot
+        .path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let label = Span::styled(
+        format!("Slot {} ({})  ", slot.index, name),
+        Style::default().fg(Color::White),
+    );
+
+    let character = Span::styled(
+        slot.character
+            .as_ref()
+            .map(|character| format!("{}  ", character_summary(character)))
+            .unwrap_or_default(),
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let detail = match (&slot.position, &slot.info) {
+        (Ok(_), Some(info)) => {
+            let near = info
+                .nearest
+                .as_ref()
+                .map(|nearest| format!(", near {} lantern", nearest.location.name))
+                .unwrap_or_default();
+            Span::styled(
+                format!("{}{}", info.map, near),
+                Style::default().fg(Color::Green),
+            )
+        }
+        (Ok(_), None) => Span::styled("Unknown map", Style::default().fg(Color::Yellow)),
+        (Err(e), _) => Span::styled(format!("✗ {}", e), Style::default().fg(Color::Red)),
+    };
+
+    ListItem::new(Line::from(vec![label, character, detail]))
+}
@@ -10,8 +10,9 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 
+use super::character_summary;
 use crate::app::App;
-use lantern_teleport_core::CurrentPosition;
+use lantern_teleport_core::{CharacterInfo, CurrentPosition, WorldState, identify_position};
 
 /// Render validation success screen
 pub fn render_validation_success(f: &mut Frame, app: &App, position: &CurrentPosition) {
@@ -42,7 +43,17 @@ pub fn render_validation_success(f: &mut Frame, app: &App, position: &CurrentPos
     f.render_widget(title, chunks[0]);
 
     // Content
-    let content = build_validation_success_content(position, app.save_file_path.as_ref());
+    let content = build_validation_success_content(
+        position,
+        app.character.as_ref(),
+        app.world_state,
+        app.has_pending_nudge()
+            .then(|| app.nudged_position())
+            .flatten()
+            .map(|preview| (app.nudge, preview)),
+        app.save_file_path.as_ref(),
+        app.status_message.as_deref(),
+    );
     let content_block = Paragraph::new(content)
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: true })
@@ -53,6 +64,14 @@ pub fn render_validation_success(f: &mut Frame, app: &App, position: &CurrentPos
     let footer = Paragraph::new(Line::from(vec![
         Span::styled("Enter", Style::default().fg(Color::Cyan)),
         Span::raw(": Continue  "),
+        Span::styled("b", Style::default().fg(Color::Cyan)),
+        Span::raw(": Bookmark position  "),
+        Span::styled("c", Style::default().fg(Color::Cyan)),
+        Span::raw(": Copy position from  "),
+        Span::styled("x/y/z", Style::default().fg(Color::Cyan)),
+        Span::raw(": Nudge (Shift: back)  "),
+        Span::styled("w", Style::default().fg(Color::Cyan)),
+        Span::raw(": Write nudge  "),
         Span::styled("Esc", Style::default().fg(Color::Cyan)),
         Span::raw(": Change file  "),
         Span::styled("q", Style::default().fg(Color::Cyan)),
@@ -65,13 +84,30 @@ pub fn render_validation_success(f: &mut Frame, app: &App, position: &CurrentPos
 /// Build validation success content
 fn build_validation_success_content(
     position: &CurrentPosition,
+    character: Option<&CharacterInfo>,
+    world_state: Option<WorldState>,
+    nudge: Option<([f32; 3], CurrentPosition)>,
     save_path: Option<&PathBuf>,
+    status_message: Option<&str>,
 ) -> Vec<Line<'static>> {
     let path_str = save_path
         .map(|p| p.to_string_lossy().to_string())
         .unwrap_or_else(|| "Unknown".to_string());
 
-    vec![
+    let (area_str, near_str) = match identify_position(position) {
+        Some(info) => (
+            info.map.area().to_string(),
+            info.nearest.map(|nearest| {
+                format!(
+                    "Near: {} lantern ({:.1} units)",
+                    nearest.location.name, nearest.distance
+                )
+            }),
+        ),
+        None => ("Unknown map".to_string(), None),
+    };
+
+    let mut lines = vec![
         Line::from(""),
         Line::from(Span::styled(
             "✓ Valid Bloodborne save file detected",
@@ -83,6 +119,20 @@ fn build_validation_success_content(
         Line::from(Span::styled("File: ", Style::default().fg(Color::Gray))),
         Line::from(Span::styled(path_str, Style::default().fg(Color::White))),
         Line::from(""),
+    ];
+
+    if let Some(character) = character {
+        lines.extend(character_lines(character));
+        if let Some(world_state) = world_state {
+            lines.push(Line::from(Span::styled(
+                format!("World State: {}", world_state),
+                Style::default().fg(Color::White),
+            )));
+        }
+        lines.push(Line::from(""));
+    }
+
+    lines.extend([
         Line::from(Span::styled(
             "Current Position:",
             Style::default()
@@ -90,10 +140,13 @@ fn build_validation_success_content(
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(Span::styled(
-            format!(
-                "Map ID: {:02X}{:02X}{:02X}{:02X}",
-                position.map_id[0], position.map_id[1], position.map_id[2], position.map_id[3]
-            ),
+            match position.map() {
+                Ok(map) => format!("Map: {} (variant {})", map, map.variant()),
+                Err(_) => format!(
+                    "Map ID: {:02X}{:02X}{:02X}{:02X}",
+                    position.map_id[0], position.map_id[1], position.map_id[2], position.map_id[3]
+                ),
+            },
             Style::default().fg(Color::White),
         )),
         Line::from(Span::styled(
@@ -103,11 +156,101 @@ fn build_validation_success_content(
             ),
             Style::default().fg(Color::White),
         )),
+        Line::from(Span::styled(
+            format!("Area: {}", area_str),
+            Style::default().fg(Color::Yellow),
+        )),
+        Line::from(Span::styled(
+            near_str.unwrap_or_default(),
+            Style::default().fg(Color::Yellow),
+        )),
+    ]);
+
+    if let Some(([dx, dy, dz], preview)) = nudge {
+        let near = identify_position(&preview)
+            .and_then(|info| info.nearest)
+            .map(|nearest| {
+                format!(
+                    "Near: {} lantern ({:.1} units)",
+                    nearest.location.name, nearest.distance
+                )
+            })
+            .unwrap_or_default();
+        lines.extend([
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("Nudge: X {:+.2}  Y {:+.2}  Z {:+.2}", dx, dy, dz),
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(
+                format!(
+                    "→ X: {:.2}  Y: {:.2}  Z: {:.2}",
+                    preview.x, preview.y, preview.z
+                ),
+                Style::default().fg(Color::Magenta),
+            )),
+            Line::from(Span::styled(near, Style::default().fg(Color::Magenta))),
+            Line::from(Span::styled(
+                "Press w to write the nudge, Esc to discard it",
+                Style::default().fg(Color::Gray),
+            )),
+        ]);
+    }
+
+    lines.extend([
         Line::from(""),
         Line::from(Span::styled(
             "Press Enter to select destination...",
             Style::default().fg(Color::Gray),
         )),
+    ]);
+
+    if let Some(message) = status_message {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            message.to_string(),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+
+    lines
+}
+
+/// Character name, level and progress, with "?" for unreadable fields
+fn character_lines(character: &CharacterInfo) -> Vec<Line<'static>> {
+    let unknown = || "?".to_string();
+    let playtime = character
+        .playtime
+        .map(|playtime| {
+            let secs = playtime.as_secs();
+            format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+        })
+        .unwrap_or_else(unknown);
+
+    vec![
+        Line::from(Span::styled(
+            "Character:",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            character_summary(character),
+            Style::default().fg(Color::White),
+        )),
+        Line::from(Span::styled(
+            format!(
+                "Insight: {}  Blood Echoes: {}  Play Time: {}",
+                character.insight.map_or_else(unknown, |v| v.to_string()),
+                character
+                    .blood_echoes
+                    .map_or_else(unknown, |v| v.to_string()),
+                playtime
+            ),
+            Style::default().fg(Color::White),
+        )),
     ]
 }
 
@@ -210,34 +353,65 @@ pub fn render_teleport_success(f: &mut Frame, app: &App) {
     f.render_widget(content_block, chunks[1]);
 
     // Footer
-    let footer = Paragraph::new(Line::from(vec![
+    let mut footer_spans = vec![
         Span::styled("Enter", Style::default().fg(Color::Cyan)),
         Span::raw(": Teleport again  "),
+    ];
+    // Edits that missed the journal cannot be undone
+    if app.undoable {
+        footer_spans.push(Span::styled("u", Style::default().fg(Color::Cyan)));
+        footer_spans.push(Span::raw(": Undo  "));
+    }
+    footer_spans.extend([
         Span::styled("Esc", Style::default().fg(Color::Cyan)),
         Span::raw(": Change file  "),
         Span::styled("q", Style::default().fg(Color::Cyan)),
         Span::raw(": Quit"),
-    ]))
-    .block(Block::default().borders(Borders::ALL));
+    ]);
+    let footer =
+        Paragraph::new(Line::from(footer_spans)).block(Block::default().borders(Borders::ALL));
     f.render_widget(footer, chunks[2]);
 }
 
 /// Build teleport success content
 fn build_teleport_success_content(app: &App) -> Vec<Line<'static>> {
-    let location_info = app
-        .selected_destination
-        .map(|loc| {
-            (
-                loc.name.to_string(),
-                loc.region.to_string(),
-                loc.x,
-                loc.y,
-                loc.z,
-            )
-        })
-        .unwrap_or_else(|| ("Unknown".to_string(), "Unknown".to_string(), 0.0, 0.0, 0.0));
+    // A copied position has no lantern; name the save it was copied from
+    let location_info = match (&app.copy_source, &app.current_position) {
+        (Some(source), Some(position)) => (
+            format!("Position of {}", source.display()),
+            identify_position(position)
+                .map(|info| info.map.area().to_string())
+                .unwrap_or_else(|| "Unknown map".to_string()),
+            position.x,
+            position.y,
+            position.z,
+        ),
+        _ => app
+            .selected_destination
+            .map(|loc| {
+                (
+                    loc.name.to_string(),
+                    loc.region.to_string(),
+                    loc.x,
+                    loc.y,
+                    loc.z,
+                )
+            })
+            .unwrap_or_else(|| ("Unknown".to_string(), "Unknown".to_string(), 0.0, 0.0, 0.0)),
+    };
 
-    vec![
+    let backup_line = match &app.last_backup {
+        Some(backup) => Line::from(Span::styled(
+            format!("Backup saved to: {}", backup.path.display()),
+            Style::default().fg(Color::Gray),
+        )),
+        None => Line::from(Span::styled(
+            "No backup was created.",
+            Style::default().fg(Color::Red),
+        )),
+    };
+
+    let mut lines = vec![
         Line::from(""),
         Line::from(Span::styled(
             "✓ Successfully teleported!",
@@ -270,12 +444,21 @@ fn build_teleport_success_content(app: &App) -> Vec<Line<'static>> {
             "Your save file has been updated.",
             Style::default().fg(Color::Gray),
         )),
+        backup_line,
         Line::from(Span::styled(
             "Load your game to spawn at the new location!",
             Style::default().fg(Color::Gray),
         )),
         Line::from(""),
-    ]
+    ];
+
+    if let Some(message) = &app.status_message {
+        lines.push(Line::from(Span::styled(
+            message.clone(),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    lines
 }
 
 /// Render teleport error screen
@@ -0,0 +1,30 @@
+//! Feed arbitrary bytes through every save file entry point.
+//!
+//! Run with `cargo +nightly fuzz run parse_save` from the repository root.
+
+#![no_main]
+
+use std::io::Cursor;
+
+use lantern_teleport_core::{
+    LOCATIONS, SaveFile, read_position, teleport_bytes, teleport_stream, validate_save_bytes,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = validate_save_bytes(data);
+    let _ = read_position(data);
+
+    let mut bytes = data.to_vec();
+    let _ = teleport_bytes(&mut bytes, &LOCATIONS[0]);
+
+    let _ = teleport_stream(Cursor::new(data.to_vec()), &LOCATIONS[0]);
+
+    if let Ok(mut save) = SaveFile::from_bytes(data.to_vec()) {
+        let _ = save.position();
+        let _ = save.map_id();
+        let _ = save.set_position(1.0, 2.0, 3.0);
+        let _ = save.set_map_id([0xFF; 4]);
+        let _ = save.teleport_to(&LOCATIONS[0]);
+    }
+});